@@ -0,0 +1,175 @@
+//! Benchmarks of the emulator core, run headless with `cargo bench`.
+//!
+//! They run a small built-in program that loops over work RAM, or the cartridge in
+//! `JAMEBOY_BENCH_ROM` if set. Throughput is reported in frames, instructions, dots or reads per
+//! second, so results stay comparable as the core gets more accurate.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use jameboy::boot::Model;
+use jameboy::bus::Bus;
+use jameboy::memory::{Address, Memory};
+use jameboy::ppu::RendererKind;
+use jameboy::Jameboy;
+use std::env;
+use std::fs;
+use std::hint::black_box;
+
+const FRAMES: usize = 10;
+const INSTRUCTIONS: usize = 10_000;
+const DOTS: usize = jameboy::ppu::DOTS_PER_FRAME;
+
+// loop:  LD HL, $C000; LD B, $40
+// inner: LD A, [HL]; ADD A, B; LD [HL+], A; DEC B; JR NZ, inner
+//        JR loop
+const PROGRAM: [u8; 13] = [
+    0x21, 0x00, 0xC0, 0x06, 0x40, 0x7E, 0x80, 0x22, 0x05, 0x20, 0xFA, 0x18, 0xF3,
+];
+
+fn rom() -> Vec<u8> {
+    if let Some(path) = env::var_os("JAMEBOY_BENCH_ROM") {
+        return fs::read(&path).unwrap_or_else(|e| panic!("Could not read {:?}: {}", path, e));
+    }
+
+    let mut rom = vec![0; 0x8000];
+    rom[0x100..0x100 + PROGRAM.len()].copy_from_slice(&PROGRAM);
+    rom
+}
+
+/// Boxed, as batches of machines held by value take LLVM minutes to compile.
+fn boot(rom: &[u8]) -> Box<Jameboy> {
+    boot_with_renderer(rom, RendererKind::Fifo)
+}
+
+fn boot_with_renderer(rom: &[u8], renderer: RendererKind) -> Box<Jameboy> {
+    let mut jameboy = Jameboy::init();
+    jameboy.load_cartridge(rom);
+    jameboy.skip_bootstrap_rom(Model::Dmg);
+    jameboy.set_renderer(renderer);
+    Box::new(jameboy)
+}
+
+/// Memory without the rest of the machine, so that only the CPU is measured.
+struct MemoryBus(Box<Memory>);
+
+impl Bus for MemoryBus {
+    fn read(&mut self, address: Address) -> u8 {
+        self.0.read(address)
+    }
+
+    fn write(&mut self, address: Address, value: u8) {
+        self.0.write(address, value);
+    }
+
+    fn tick(&mut self) {}
+
+    fn peek(&self, address: Address) -> u8 {
+        self.0.read(address)
+    }
+
+    fn poke(&mut self, address: Address, value: u8) {
+        self.0.write(address, value);
+    }
+}
+
+fn bench_run_frame(c: &mut Criterion, rom: &[u8]) {
+    let mut group = c.benchmark_group("jameboy");
+    group.throughput(Throughput::Elements(FRAMES as u64));
+    for (name, renderer) in [
+        ("run_frame", RendererKind::Fifo),
+        ("run_frame_scanline", RendererKind::Scanline),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || boot_with_renderer(rom, renderer),
+                |jameboy| {
+                    for _ in 0..FRAMES {
+                        jameboy.run_frame();
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    // The whole machine, for comparison with the CPU on its own
+    group.throughput(Throughput::Elements(INSTRUCTIONS as u64));
+    group.bench_function("step", |b| {
+        b.iter_batched_ref(
+            || boot(rom),
+            |jameboy| {
+                for _ in 0..INSTRUCTIONS {
+                    black_box(jameboy.step());
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_cpu_step(c: &mut Criterion, rom: &[u8]) {
+    let mut group = c.benchmark_group("cpu");
+    group.throughput(Throughput::Elements(INSTRUCTIONS as u64));
+    group.bench_function("step", |b| {
+        b.iter_batched_ref(
+            || {
+                let jameboy = boot(rom);
+                (jameboy.cpu, MemoryBus(Box::new(jameboy.memory)))
+            },
+            |(cpu, bus)| {
+                for _ in 0..INSTRUCTIONS {
+                    cpu.step(bus);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_ppu_step(c: &mut Criterion, rom: &[u8]) {
+    let mut group = c.benchmark_group("ppu");
+    group.throughput(Throughput::Elements(DOTS as u64));
+    for (name, renderer) in [
+        ("step", RendererKind::Fifo),
+        ("step_scanline", RendererKind::Scanline),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || boot_with_renderer(rom, renderer),
+                |jameboy| {
+                    for _ in 0..DOTS {
+                        jameboy.ppu.step(&mut jameboy.memory);
+                    }
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_memory_read(c: &mut Criterion, rom: &[u8]) {
+    let jameboy = boot(rom);
+    let mut group = c.benchmark_group("memory");
+    group.throughput(Throughput::Elements(0x10000));
+    group.bench_function("read", |b| {
+        b.iter(|| {
+            for address in 0..=u16::MAX {
+                black_box(jameboy.memory.read(Address(black_box(address))));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_core(c: &mut Criterion) {
+    let rom = rom();
+    bench_run_frame(c, &rom);
+    bench_cpu_step(c, &rom);
+    bench_ppu_step(c, &rom);
+    bench_memory_read(c, &rom);
+}
+
+criterion_group!(benches, bench_core);
+criterion_main!(benches);
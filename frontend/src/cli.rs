@@ -0,0 +1,174 @@
+use crate::palette::Palette;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use jameboy::boot::Model;
+use jameboy::ppu::RendererKind;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "jameboy", about = "A work in progress Game Boy emulator")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Runs a ROM in the debugger window
+    Gui {
+        #[command(flatten)]
+        machine: MachineArgs,
+        #[command(flatten)]
+        display: DisplayArgs,
+    },
+    /// Runs a ROM without a window until a frame or cycle limit is reached
+    Run {
+        #[command(flatten)]
+        machine: MachineArgs,
+        #[command(flatten)]
+        display: DisplayArgs,
+        /// Stop after this many frames, or when the movie ends with --play-movie
+        #[arg(long, required_unless_present_any = ["cycles", "play_movie"])]
+        frames: Option<usize>,
+        /// Stop after this many M-cycles
+        #[arg(long)]
+        cycles: Option<usize>,
+        /// Write the final frame to this PNG
+        #[arg(long, value_name = "PATH")]
+        screenshot: Option<PathBuf>,
+        /// Record every frame to this animated GIF
+        #[arg(long, value_name = "PATH")]
+        record_frames: Option<PathBuf>,
+        /// First frame to record with --record-frames
+        #[arg(long, value_name = "FRAME", default_value_t = 0, requires = "record_frames")]
+        record_start: usize,
+    },
+    /// Runs Mooneye test ROMs and prints a pass/fail table
+    Mooneye {
+        /// A test ROM, or a directory searched recursively for them
+        path: PathBuf,
+        /// Give up on a ROM after this many frames without a result
+        #[arg(long, value_name = "FRAMES", default_value_t = 1200)]
+        timeout: usize,
+    },
+    /// Writes a gameboy-doctor log, optionally comparing it against a reference log as it runs
+    Doctor {
+        /// Path to the cartridge ROM
+        rom: PathBuf,
+        /// Where to write the log
+        #[arg(long, value_name = "PATH", default_value = "doctor.out")]
+        output: PathBuf,
+        /// Stop at the first line that differs from this reference log
+        #[arg(long, value_name = "PATH")]
+        compare: Option<PathBuf>,
+        /// Let the CPU read the real LY instead of 0x90, which gameboy-doctor logs assume
+        #[arg(long)]
+        no_ly_stub: bool,
+    },
+    /// Prints the disassembly of a ROM
+    Disasm {
+        /// Path to the cartridge ROM
+        rom: PathBuf,
+    },
+}
+
+#[derive(Args)]
+pub struct MachineArgs {
+    /// Path to the cartridge ROM
+    pub rom: PathBuf,
+    /// Path to the bootstrap ROM
+    #[arg(long, value_name = "PATH", default_value = "./roms/bootstrap.gb")]
+    pub boot_rom: PathBuf,
+    /// Start from the post-boot state instead of running the bootstrap ROM
+    #[arg(long, conflicts_with = "boot_rom")]
+    pub no_boot_rom: bool,
+    /// Hardware model whose post-boot state is used with --no-boot-rom
+    #[arg(long, value_enum, default_value_t = ModelArg::Dmg)]
+    pub model: ModelArg,
+    /// Fill work RAM and high RAM with garbage from this seed at power on, instead of zeroes
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// How the PPU draws each scanline
+    #[arg(long, value_enum, default_value_t = RendererArg::Fifo)]
+    pub renderer: RendererArg,
+    /// Directory that printer output, save states and screenshots are written to
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    pub save_dir: PathBuf,
+    #[command(flatten)]
+    pub link: LinkArgs,
+    #[command(flatten)]
+    pub movie: MovieArgs,
+}
+
+#[derive(Args)]
+#[group(multiple = false)]
+pub struct MovieArgs {
+    /// Record the input of every frame from power on into this movie file
+    #[arg(long, value_name = "PATH")]
+    pub record_movie: Option<PathBuf>,
+    /// Replay a movie. The model, seed, renderer and whether to boot from the bootstrap ROM come from
+    /// the movie
+    #[arg(long, value_name = "PATH")]
+    pub play_movie: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ModelArg {
+    /// Original Game Boy
+    Dmg,
+    /// Game Boy Pocket
+    Mgb,
+    /// Game Boy Color
+    Cgb,
+}
+
+impl From<ModelArg> for Model {
+    fn from(model: ModelArg) -> Self {
+        match model {
+            ModelArg::Dmg => Model::Dmg,
+            ModelArg::Mgb => Model::Mgb,
+            ModelArg::Cgb => Model::Cgb,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RendererArg {
+    /// A pixel per dot through the pixel FIFOs, as the hardware draws
+    Fifo,
+    /// Whole scanlines at once, faster but misses changes made part way through a scanline
+    Scanline,
+}
+
+impl From<RendererArg> for RendererKind {
+    fn from(renderer: RendererArg) -> Self {
+        match renderer {
+            RendererArg::Fifo => RendererKind::Fifo,
+            RendererArg::Scanline => RendererKind::Scanline,
+        }
+    }
+}
+
+#[derive(Args)]
+#[group(multiple = false)]
+pub struct LinkArgs {
+    /// Wait for another instance to connect a link cable on this address
+    #[arg(long, value_name = "ADDRESS")]
+    pub link_listen: Option<String>,
+    /// Connect a link cable to an instance listening on this address
+    #[arg(long, value_name = "ADDRESS")]
+    pub link_connect: Option<String>,
+    /// Attach a Game Boy Printer to the serial port
+    #[arg(long)]
+    pub printer: bool,
+}
+
+#[derive(Args, Clone)]
+pub struct DisplayArgs {
+    /// Integer scale of the display
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..=8))]
+    pub scale: u32,
+    /// Colours used for the four shades
+    #[arg(long, value_enum, default_value_t = Palette::Gray)]
+    pub palette: Palette,
+}
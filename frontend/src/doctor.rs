@@ -0,0 +1,145 @@
+use jameboy::boot::Model;
+use jameboy::memory::Address;
+use jameboy::opcode;
+use jameboy::Jameboy;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// gameboy-doctor logs are taken with LY reading as 0x90, the start of VBlank.
+const DOCTOR_LY: u8 = 0x90;
+/// Matching lines printed before the first divergence, and reference lines printed after it.
+const CONTEXT_LINES: usize = 5;
+
+/// Writes a gameboy-doctor log of every instruction to `output`. With a `reference` log, stops at
+/// the first line that differs from it, or once it runs out.
+pub fn doctor(rom: &[u8], output: &Path, reference: Option<&Path>, ly_stub: bool) -> Result<(), String> {
+    let file = File::create(output).map_err(|e| format!("could not create {}: {}", output.display(), e))?;
+    let mut file = BufWriter::new(file);
+    let mut reference = match reference {
+        Some(path) => {
+            let file = File::open(path).map_err(|e| format!("could not open {}: {}", path.display(), e))?;
+            Some((path, BufReader::new(file).lines()))
+        }
+        None => None,
+    };
+
+    let mut jameboy = Jameboy::init();
+    jameboy.load_cartridge(rom);
+    jameboy.skip_bootstrap_rom(Model::Dmg);
+    if ly_stub {
+        jameboy.set_ly_stub(Some(DOCTOR_LY));
+    }
+
+    let mut context = VecDeque::with_capacity(CONTEXT_LINES);
+    let mut line_number = 0;
+    loop {
+        // Don't log when we hit CB/prefix opcode or we break gameboy-doctor
+        if !jameboy.cpu.prefixed {
+            let line = log_line(&jameboy);
+            writeln!(file, "{}", line).map_err(|e| format!("could not write {}: {}", output.display(), e))?;
+            line_number += 1;
+
+            if let Some((path, lines)) = &mut reference {
+                let expected = match lines.next() {
+                    Some(expected) => {
+                        expected.map_err(|e| format!("could not read {}: {}", path.display(), e))?
+                    }
+                    None => {
+                        println!("Matched all {} lines of {}", line_number - 1, path.display());
+                        return Ok(());
+                    }
+                };
+
+                if expected.trim_end() != line {
+                    let following = lines
+                        .by_ref()
+                        .take(CONTEXT_LINES)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+                    print_divergence(line_number, &context, &expected, &line, &following);
+                    return Err(format!("log differs from {} at line {}", path.display(), line_number));
+                }
+
+                if context.len() == CONTEXT_LINES {
+                    context.pop_front();
+                }
+                context.push_back(line);
+            }
+        }
+
+        jameboy.step();
+    }
+}
+
+fn log_line(jameboy: &Jameboy) -> String {
+    let cpu = &jameboy.cpu;
+    let pcmem = |offset: u16| jameboy.memory.read(Address(cpu.pc.wrapping_add(offset)));
+
+    format!(
+        "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+        cpu.a, cpu.f, cpu.b, cpu.c, cpu.d, cpu.e, cpu.h, cpu.l, cpu.sp, cpu.pc,
+        pcmem(0), pcmem(1), pcmem(2), pcmem(3)
+    )
+}
+
+/// Prints the log around the first differing line, diff style, with `following` being what the
+/// reference did next.
+fn print_divergence(
+    line_number: usize,
+    context: &VecDeque<String>,
+    expected: &str,
+    actual: &str,
+    following: &[String],
+) {
+    let first_context_line = line_number - context.len();
+    for (i, line) in context.iter().enumerate() {
+        println!("{:>8}  {}", first_context_line + i, line);
+    }
+    println!("{:>8}- {}", line_number, expected.trim_end());
+    println!("{:>8}+ {}", line_number, actual);
+    for (i, line) in following.iter().enumerate() {
+        println!("{:>8}- {}", line_number + 1 + i, line.trim_end());
+    }
+    println!();
+
+    if let Some(previous) = context.back() {
+        if let Some(instruction) = instruction(previous) {
+            println!("After executing {} at PC:{}", instruction, field(previous, "PC").unwrap_or("?"));
+        }
+    }
+
+    for (name, expected_value) in expected.split_whitespace().filter_map(|f| f.split_once(':')) {
+        match field(actual, name) {
+            Some(actual_value) if actual_value == expected_value => {}
+            actual_value => println!(
+                "  {:<6} expected {}, got {}",
+                name,
+                expected_value,
+                actual_value.unwrap_or("nothing")
+            ),
+        }
+    }
+}
+
+fn field<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    line.split_whitespace()
+        .filter_map(|f| f.split_once(':'))
+        .find(|(field_name, _)| *field_name == name)
+        .map(|(_, value)| value)
+}
+
+/// The mnemonic of the instruction at the start of a log line's PCMEM.
+fn instruction(line: &str) -> Option<&'static str> {
+    let bytes: Vec<u8> = field(line, "PCMEM")?
+        .split(',')
+        .filter_map(|byte| u8::from_str_radix(byte, 16).ok())
+        .collect();
+
+    let opcode = match bytes.first()? {
+        0xCB => opcode::decode_prefixed(*bytes.get(1)?),
+        byte => opcode::decode(*byte),
+    };
+    opcode.map(|opcode| opcode.mnemonic)
+}
@@ -0,0 +1,23 @@
+use jameboy::joypad::Buttons;
+
+const KEY_RIGHT: egui::Key = egui::Key::D;
+const KEY_LEFT: egui::Key = egui::Key::A;
+const KEY_UP: egui::Key = egui::Key::W;
+const KEY_DOWN: egui::Key = egui::Key::S;
+const KEY_A: egui::Key = egui::Key::J;
+const KEY_B: egui::Key = egui::Key::K;
+const KEY_SELECT: egui::Key = egui::Key::Enter;
+const KEY_START: egui::Key = egui::Key::Escape;
+
+pub fn read_buttons(ctx: &egui::Context) -> Buttons {
+    ctx.input(|i| Buttons {
+        right: i.key_pressed(KEY_RIGHT),
+        left: i.key_pressed(KEY_LEFT),
+        up: i.key_pressed(KEY_UP),
+        down: i.key_pressed(KEY_DOWN),
+        a: i.key_pressed(KEY_A),
+        b: i.key_pressed(KEY_B),
+        select: i.key_pressed(KEY_SELECT),
+        start: i.key_pressed(KEY_START),
+    })
+}
@@ -0,0 +1,796 @@
+mod cli;
+mod doctor;
+mod joypad;
+mod movie;
+mod pacer;
+mod palette;
+mod save_states;
+mod screenshot;
+mod video;
+
+use crate::cli::{Cli, Command, DisplayArgs, LinkArgs, MachineArgs};
+use crate::movie::MovieInput;
+use crate::pacer::{Pacer, Speed};
+use crate::save_states::{SaveStates, SLOTS};
+use crate::screenshot::Screenshots;
+use crate::video::VideoRecorder;
+
+use clap::Parser;
+use eframe::egui;
+use egui::{Align, ColorImage};
+use egui_extras::{Column, TableBuilder, TableRow};
+use jameboy::boot::Model;
+use jameboy::cpu::RegisterWide;
+use jameboy::disassembly::{self, Instruction};
+use jameboy::link::TcpLink;
+use jameboy::memory::Address;
+use jameboy::mooneye::{self, Outcome};
+use jameboy::movie::{self as movies, Movie, MovieSettings};
+use jameboy::printer::Printer;
+use jameboy::rewind::Rewind;
+use jameboy::serial::{LinkEvent, SerialLink};
+use jameboy::timeline::Timeline;
+use jameboy::Jameboy;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+type ROM = Vec<u8>;
+
+const KEY_FAST_FORWARD: egui::Key = egui::Key::Space;
+const KEY_FRAME_ADVANCE: egui::Key = egui::Key::N;
+const KEY_REWIND: egui::Key = egui::Key::R;
+
+// A snapshot every other frame, about 20 seconds of rewind
+const REWIND_SNAPSHOTS: usize = 600;
+const REWIND_INTERVAL: usize = 2;
+
+// A checkpoint every 4096 instructions, the timeline's unit of steps. Instructions average a few
+// M-cycles, so that's around half a frame and 256 checkpoints hold about 2 seconds of reverse stepping
+const TIMELINE_CHECKPOINTS: usize = 256;
+const TIMELINE_INTERVAL: u64 = 4096;
+
+enum State {
+    Paused,
+    Running,
+}
+
+struct Debugger {
+    run_to_pc: Option<u16>,
+    breakpoints: BTreeSet<u16>,
+    state: State,
+    timeline: Timeline,
+    rewind: Rewind,
+    input: MovieInput,
+    // Set when resuming so the breakpoint we're stopped at doesn't immediately stop us again
+    leaving_breakpoint: bool,
+}
+
+impl Debugger {
+    fn init(input: MovieInput) -> Self {
+        Self {
+            run_to_pc: None,
+            breakpoints: BTreeSet::new(),
+            state: State::Paused,
+            timeline: Timeline::init(TIMELINE_CHECKPOINTS, TIMELINE_INTERVAL),
+            rewind: Rewind::init(REWIND_SNAPSHOTS, REWIND_INTERVAL),
+            input,
+            leaving_breakpoint: false,
+        }
+    }
+
+    fn resume(&mut self) {
+        self.state = State::Running;
+        self.leaving_breakpoint = true;
+    }
+
+    fn step(&mut self, jameboy: &mut Jameboy) {
+        if let Some(run_to_pc) = self.run_to_pc {
+            if run_to_pc == jameboy.cpu.pc {
+                self.state = State::Paused;
+                self.run_to_pc = None;
+                return;
+            }
+        }
+
+        if !self.leaving_breakpoint && self.breakpoints.contains(&jameboy.cpu.pc) {
+            self.state = State::Paused;
+            return;
+        }
+        self.leaving_breakpoint = false;
+
+        self.execute(jameboy);
+    }
+
+    /// Executes a single instruction, ignoring breakpoints.
+    fn execute(&mut self, jameboy: &mut Jameboy) {
+        if let Some(buttons) = self.input.frame_input(jameboy.ppu.frame_count) {
+            self.timeline.set_input(jameboy, buttons);
+        }
+        self.timeline.step(jameboy);
+    }
+
+    fn toggle_breakpoint(&mut self, address: u16) {
+        if !self.breakpoints.remove(&address) {
+            self.breakpoints.insert(address);
+        }
+    }
+
+    /// Goes back to the last time a breakpoint was hit.
+    fn reverse_continue(&mut self, jameboy: &mut Jameboy) {
+        let breakpoints = &self.breakpoints;
+        if !self.timeline.reverse_continue(jameboy, |pc| breakpoints.contains(&pc)) {
+            println!("No breakpoint was hit within the recorded history");
+        }
+    }
+
+    /// Goes back to the previous rewind snapshot.
+    fn rewind(&mut self, jameboy: &mut Jameboy) {
+        if let Err(e) = self.rewind.rewind(jameboy) {
+            eprintln!("error: could not rewind: {}", e);
+        }
+        self.timeline.clear();
+    }
+
+    /// Runs to the end of the current frame, stopping early at the run-to-PC target.
+    fn run_frame(&mut self, jameboy: &mut Jameboy) {
+        let frame_count = jameboy.ppu.frame_count;
+        while jameboy.ppu.frame_count == frame_count {
+            if let State::Paused = self.state {
+                return;
+            }
+            self.step(jameboy);
+        }
+        self.rewind.record(jameboy);
+    }
+
+    /// Runs a single frame from a paused state.
+    fn advance_frame(&mut self, jameboy: &mut Jameboy) {
+        self.resume();
+        self.run_frame(jameboy);
+        self.state = State::Paused;
+    }
+}
+
+/// State of the GUI outside of the debugger.
+struct Gui {
+    display: DisplayArgs,
+    pacer: Pacer,
+    save_states: SaveStates,
+    screenshots: Screenshots,
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Gui { machine, display } => gui(&machine, display),
+        Command::Run {
+            machine,
+            display,
+            frames,
+            cycles,
+            screenshot,
+            record_frames,
+            record_start,
+        } => run(
+            &machine,
+            &display,
+            frames,
+            cycles,
+            screenshot.as_deref(),
+            record_frames.as_deref().map(|path| (path, record_start)),
+        ),
+        Command::Mooneye { path, timeout } => mooneye(&path, timeout),
+        Command::Doctor {
+            rom,
+            output,
+            compare,
+            no_ly_stub,
+        } => open_rom(&rom)
+            .and_then(|rom| doctor::doctor(&rom, &output, compare.as_deref(), !no_ly_stub)),
+        Command::Disasm { rom } => disasm(&rom),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Builds a machine with the cartridge, bootstrap ROM and serial peripheral selected on the command
+/// line, along with where its input comes from.
+fn load_machine(args: &MachineArgs) -> Result<(Jameboy, ROM, MovieInput), String> {
+    let rom = open_rom(&args.rom)?;
+
+    let movie = match &args.movie.play_movie {
+        Some(path) => {
+            let data = std::fs::read(path)
+                .map_err(|e| format!("could not read movie {}: {}", path.display(), e))?;
+            let movie = Movie::load(&data)
+                .map_err(|e| format!("could not load movie {}: {}", path.display(), e))?;
+            Some(movie)
+        }
+        None => None,
+    };
+
+    let use_bootstrap_rom = match &movie {
+        Some(movie) => movie.settings.bootstrap_rom_hash.is_some(),
+        None => !args.no_boot_rom,
+    };
+    let bootstrap_rom = match use_bootstrap_rom {
+        true => Some(
+            open_rom(&args.boot_rom)
+                .map_err(|e| format!("{} (pass --no-boot-rom to start without one)", e))?,
+        ),
+        false => None,
+    };
+
+    let settings = match &movie {
+        Some(movie) => movie.settings,
+        None => MovieSettings {
+            rom_hash: movies::rom_hash(&rom),
+            bootstrap_rom_hash: bootstrap_rom.as_deref().map(movies::rom_hash),
+            model: args.model.into(),
+            seed: args.seed,
+            renderer: args.renderer.into(),
+        },
+    };
+    let mut jameboy = movies::power_on(&settings, &rom, bootstrap_rom.as_deref())
+        .ok_or("the ROMs don't match the ones the movie was recorded with")?;
+
+    if let Some(link) = open_link(&args.link, &args.save_dir)? {
+        jameboy.connect_serial(link);
+    }
+
+    let input = match (movie, &args.movie.record_movie) {
+        (Some(movie), _) => MovieInput::play(movie),
+        (None, Some(path)) => MovieInput::record(Movie::init(settings), path.clone()),
+        (None, None) => MovieInput::live(),
+    };
+
+    Ok((jameboy, rom, input))
+}
+
+fn gui(args: &MachineArgs, display: DisplayArgs) -> Result<(), String> {
+    let (mut jameboy, rom, input) = load_machine(args)?;
+    let mut debugger = Debugger::init(input);
+    let disassembly = disassembly::disassemble(&rom);
+
+    let options = eframe::NativeOptions {
+        vsync: false,
+        ..Default::default()
+    };
+
+    let rom_name = rom_name(&args.rom);
+    let mut gui = Gui {
+        display,
+        pacer: Pacer::init(),
+        save_states: SaveStates::init(&args.save_dir, &rom_name),
+        screenshots: Screenshots::init(&args.save_dir, &rom_name),
+    };
+    eframe::run_simple_native("jameboy", options, move |ctx, _frame| {
+        if ctx.input(|i| i.viewport().close_requested()) {
+            if let Err(e) = debugger.input.save() {
+                eprintln!("error: {}", e);
+            }
+            if gui.screenshots.recording() {
+                gui.screenshots.toggle_recording(&gui.display);
+            }
+        }
+
+        if gui.save_states.handle_hotkeys(ctx, &mut jameboy) {
+            debugger.timeline.clear();
+        }
+        gui.screenshots.handle_hotkeys(ctx, &jameboy, &gui.display);
+
+        let (fast_forward, frame_advance, rewinding) = ctx.input(|i| {
+            (
+                i.key_down(KEY_FAST_FORWARD),
+                i.key_pressed(KEY_FRAME_ADVANCE),
+                i.key_down(KEY_REWIND),
+            )
+        });
+        if frame_advance {
+            debugger.state = State::Paused;
+            debugger.input.set_held(joypad::read_buttons(ctx));
+            debugger.advance_frame(&mut jameboy);
+            gui.screenshots.capture(&jameboy);
+        }
+
+        match debugger.state {
+            State::Running if rewinding => {
+                gui.pacer.run_due_frames(fast_forward, || {
+                    debugger.rewind(&mut jameboy);
+                    gui.screenshots.capture(&jameboy);
+                });
+            }
+            State::Running => {
+                debugger.input.set_held(joypad::read_buttons(ctx));
+                gui.pacer.run_due_frames(fast_forward, || {
+                    debugger.run_frame(&mut jameboy);
+                    gui.screenshots.capture(&jameboy);
+                });
+            }
+            State::Paused => gui.pacer.pause(),
+        }
+
+        report_link_events(&mut jameboy);
+        render(ctx, &mut jameboy, &mut debugger, &disassembly, &mut gui);
+        if let State::Running = debugger.state {
+            ctx.request_repaint_after(gui.pacer.time_until_next_frame());
+        }
+    })
+    .map_err(|e| format!("could not open window: {}", e))
+}
+
+/// Runs a ROM without a window until the frame or cycle limit is reached.
+fn run(
+    args: &MachineArgs,
+    display: &DisplayArgs,
+    frames: Option<usize>,
+    cycles: Option<usize>,
+    screenshot_path: Option<&Path>,
+    recording: Option<(&Path, usize)>,
+) -> Result<(), String> {
+    let (mut jameboy, _, mut input) = load_machine(args)?;
+    let frames = match (frames, cycles) {
+        (None, None) => input.movie_len(),
+        _ => frames,
+    };
+
+    let mut recorder = match recording {
+        Some((path, _)) => Some(VideoRecorder::start(path, display, display.scale)?),
+        None => None,
+    };
+    let record_start = recording.map_or(0, |(_, start)| start);
+
+    let mut elapsed_cycles = 0;
+    while frames.is_none_or(|frames| jameboy.ppu.frame_count < frames)
+        && cycles.is_none_or(|cycles| elapsed_cycles < cycles)
+    {
+        if let Some(buttons) = input.frame_input(jameboy.ppu.frame_count) {
+            jameboy.set_input(buttons);
+        }
+
+        let frame_count = jameboy.ppu.frame_count;
+        elapsed_cycles += jameboy.step();
+
+        if jameboy.ppu.frame_count != frame_count {
+            report_link_events(&mut jameboy);
+        }
+        if let Some(recorder) = &mut recorder {
+            if jameboy.ppu.frame_count != frame_count && jameboy.ppu.frame_count > record_start {
+                recorder.capture(&jameboy)?;
+            }
+        }
+    }
+    report_link_events(&mut jameboy);
+
+    println!("Ran {} frames ({} cycles)", jameboy.ppu.frame_count, elapsed_cycles);
+    input.save()?;
+    if let Some(recorder) = recorder {
+        println!("Recorded {} frames to {}", recorder.frames(), recorder.path().display());
+    }
+
+    if let Some(path) = screenshot_path {
+        screenshot::save(jameboy.framebuffer(), display, display.scale, path)?;
+    }
+
+    Ok(())
+}
+
+/// Runs every Mooneye test ROM under `path` and prints how each one went.
+fn mooneye(path: &Path, timeout_frames: usize) -> Result<(), String> {
+    let mut rom_paths = Vec::new();
+    find_roms(path, &mut rom_paths)?;
+    rom_paths.sort();
+    if rom_paths.is_empty() {
+        return Err(format!("no .gb files found in {}", path.display()));
+    }
+
+    let timeout_cycles = timeout_frames * jameboy::ppu::DOTS_PER_FRAME / 4;
+    let mut passed = 0;
+    for rom_path in &rom_paths {
+        let rom = open_rom(rom_path)?;
+        let mut jameboy = Jameboy::init();
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+
+        let name = rom_path.strip_prefix(path).unwrap_or(rom_path).display();
+        match mooneye::run(&mut jameboy, timeout_cycles) {
+            Outcome::Pass => {
+                passed += 1;
+                println!("PASS     {}", name);
+            }
+            Outcome::Fail([b, c, d, e, h, l]) => println!(
+                "FAIL     {} (B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X})",
+                name, b, c, d, e, h, l
+            ),
+            Outcome::Timeout => println!("TIMEOUT  {}", name),
+        }
+    }
+
+    println!("{} of {} passed", passed, rom_paths.len());
+    match passed == rom_paths.len() {
+        true => Ok(()),
+        false => Err(format!("{} tests did not pass", rom_paths.len() - passed)),
+    }
+}
+
+fn find_roms(path: &Path, rom_paths: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !path.is_dir() {
+        rom_paths.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let entries =
+        std::fs::read_dir(path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    for entry in entries {
+        let entry_path = entry
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?
+            .path();
+        if entry_path.is_dir() || entry_path.extension().is_some_and(|extension| extension == "gb") {
+            find_roms(&entry_path, rom_paths)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn disasm(rom_path: &Path) -> Result<(), String> {
+    let rom = open_rom(rom_path)?;
+    for instruction in disassembly::disassemble(&rom).iter() {
+        match &instruction.opcode {
+            Some(opcode) => println!("{:04x}    {}", instruction.address, opcode.mnemonic),
+            None => println!("{:04x}    UNKNOWN", instruction.address),
+        }
+    }
+
+    Ok(())
+}
+
+fn render(
+    ctx: &egui::Context,
+    jameboy: &mut Jameboy,
+    debugger: &mut Debugger,
+    disassembly: &Vec<Instruction>,
+    gui: &mut Gui,
+) {
+    egui::CentralPanel::default().show(ctx, |ui| {
+        egui::Window::new("CPU").show(ctx, |ui| {
+            ui.heading("Registers");
+            ui.label(format!(
+                "AF: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::AF)
+            ));
+            ui.label(format!(
+                "BC: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::BC)
+            ));
+            ui.label(format!(
+                "DE: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::DE)
+            ));
+            ui.label(format!(
+                "HL: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::HL)
+            ));
+            ui.label(format!(
+                "PC: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::PC)
+            ));
+            ui.label(format!(
+                "SP: {:04x}",
+                jameboy.cpu.read_register_wide(RegisterWide::SP)
+            ));
+            ui.add_space(5.0);
+
+            ui.heading("Flags");
+            ui.horizontal(|ui| {
+                let mut flags = jameboy.cpu.read_flags();
+                ui.add_enabled(false, egui::Checkbox::new(&mut flags.zero, "Z"));
+                ui.add_enabled(false, egui::Checkbox::new(&mut flags.subtract, "S"));
+                ui.add_enabled(false, egui::Checkbox::new(&mut flags.half_carry, "HC"));
+                ui.add_enabled(false, egui::Checkbox::new(&mut flags.carry, "C"));
+            });
+
+            ui.heading("IME");
+            ui.horizontal(|ui| {
+                let mut ime = jameboy.cpu.ime;
+                ui.add_enabled(false, egui::Checkbox::new(&mut ime, "IME"));
+            });
+        });
+
+        egui::Window::new("Disassembly").show(ctx, |ui| {
+            let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+            let table = TableBuilder::new(ui)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .column(Column::auto())
+                .column(Column::auto())
+                .resizable(true);
+
+            let pc = jameboy.cpu.read_register_wide(RegisterWide::PC);
+
+            table
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Address");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Instruction");
+                    });
+                })
+                .body(|body| {
+                    body.rows(text_height, disassembly.len(), |mut row| {
+                        let row_index = row.index();
+                        let instruction = &disassembly[row_index];
+                        row.col(|ui| {
+                            if Address(pc) == instruction.address {
+                                let max_rect = ui.max_rect();
+                                let stripe_rect = max_rect.expand2(0.5 * ui.spacing().item_spacing);
+
+                                ui.painter().rect_filled(
+                                    stripe_rect,
+                                    0.0,
+                                    ui.visuals().extreme_bg_color,
+                                );
+                            }
+                            let marker = match debugger.breakpoints.contains(&instruction.address.0) {
+                                true => "●",
+                                false => " ",
+                            };
+                            ui.label(format!("{} {:04x}", marker, instruction.address));
+                        });
+                        row.col(|ui| {
+                            if Address(pc) == instruction.address {
+                                let max_rect = ui.max_rect();
+                                let stripe_rect = max_rect.expand2(0.5 * ui.spacing().item_spacing);
+
+                                ui.painter().rect_filled(
+                                    stripe_rect,
+                                    0.0,
+                                    ui.visuals().extreme_bg_color,
+                                );
+                            }
+
+                            let label = match &instruction.opcode {
+                                Some(op) => op.mnemonic.to_string(),
+                                None => "UNKNOWN".to_string(),
+                            };
+                            ui.label(label);
+                        });
+
+                        let response = row.response().interact(egui::Sense::click());
+                        if response.double_clicked() {
+                            debugger.resume();
+                            debugger.run_to_pc = Some(instruction.address.0);
+                        }
+                        if response.secondary_clicked() {
+                            debugger.toggle_breakpoint(instruction.address.0);
+                        }
+                    });
+                });
+
+            ui.add_space(10.0);
+            ui.label("Right click an instruction to toggle a breakpoint");
+            ui.horizontal(|ui| match debugger.state {
+                State::Paused => {
+                    let rewind = ui.add_enabled(!debugger.rewind.is_empty(), egui::Button::new("⏪"));
+                    if rewind.on_hover_text("Step back to the previous rewind snapshot").clicked() {
+                        debugger.rewind(jameboy);
+                    }
+                    let has_history = debugger.timeline.position() > debugger.timeline.start().unwrap_or(0);
+                    let reverse_continue = ui.add_enabled(has_history, egui::Button::new("⏴"));
+                    if reverse_continue
+                        .on_hover_text("Reverse-continue to the last breakpoint hit")
+                        .clicked()
+                    {
+                        debugger.reverse_continue(jameboy);
+                    }
+                    let step_back = ui.add_enabled(has_history, egui::Button::new("⏮"));
+                    if step_back.on_hover_text("Step back one instruction").clicked() {
+                        debugger.timeline.step_back(jameboy);
+                    }
+                    if ui.button("⏵").clicked() {
+                        debugger.resume();
+                    }
+                    if ui.button("⏭").clicked() {
+                        debugger.execute(jameboy);
+                    }
+                    if ui.button("⏩").on_hover_text("Advance one frame (N)").clicked() {
+                        debugger.advance_frame(jameboy);
+                    }
+                }
+                State::Running => {
+                    ui.add_enabled(false, egui::Button::new("⏪"));
+                    ui.add_enabled(false, egui::Button::new("⏴"));
+                    ui.add_enabled(false, egui::Button::new("⏮"));
+                    if ui.button("⏸").clicked() {
+                        debugger.state = State::Paused;
+                    }
+                    ui.add_enabled(false, egui::Button::new("⏭"));
+                    ui.add_enabled(false, egui::Button::new("⏩"));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Speed");
+                egui::ComboBox::from_id_source("speed")
+                    .selected_text(gui.pacer.speed.label())
+                    .show_ui(ui, |ui| {
+                        for speed in Speed::ALL {
+                            ui.selectable_value(&mut gui.pacer.speed, speed, speed.label());
+                        }
+                    });
+            });
+        });
+
+        egui::Window::new("Save states").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Slot");
+                egui::ComboBox::from_id_source("save_state_slot")
+                    .selected_text(gui.save_states.slot.to_string())
+                    .show_ui(ui, |ui| {
+                        for slot in 1..=SLOTS {
+                            let label = match gui.save_states.exists(slot) {
+                                true => format!("{} (saved)", slot),
+                                false => slot.to_string(),
+                            };
+                            ui.selectable_value(&mut gui.save_states.slot, slot, label);
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Save").on_hover_text("F5").clicked() {
+                    gui.save_states.save(jameboy);
+                }
+                if ui.button("Load").on_hover_text("F8").clicked() && gui.save_states.load(jameboy) {
+                    debugger.timeline.clear();
+                }
+            });
+            ui.label(&gui.save_states.status);
+        });
+
+        egui::Window::new("Memory").show(ctx, |ui| {
+            let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+            let table = TableBuilder::new(ui)
+                .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                .columns(Column::auto(), 1 + 16); // 1 for offset, 16 for bytes
+
+            table
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Offset");
+                    });
+                    for i in 0..16 {
+                        header.col(|ui| {
+                            ui.strong(format!("{:02X}", i));
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(text_height, 0x10000 / 16, |mut row| {
+                        let row_index = row.index();
+                        row.col(|ui| {
+                            ui.label(format!("{:04X}", row_index * 16));
+                        });
+                        for i in 0..16 {
+                            row.col(|ui| {
+                                let address = Address((row_index * 16 + i) as u16);
+                                let value = jameboy.memory.read(address);
+                                ui.label(format!("{:02X}", value));
+                            });
+                        }
+                    });
+                });
+        });
+
+        egui::Window::new("Tiles").show(ctx, |ui| {
+            let image = &jameboy.ppu.get_tile_buffer(&jameboy.memory);
+            let image = &image::imageops::resize(
+                image,
+                image.width() * 4,
+                image.height() * 4,
+                image::imageops::FilterType::Nearest,
+            );
+            let size = (image.width() as usize, image.height() as usize);
+            let image = ColorImage::from_gray(size.into(), image);
+            let texture = ctx.load_texture("LCD", image, egui::TextureOptions::default());
+
+            ui.image(&texture);
+        });
+
+        let image = &screenshot::render(jameboy.framebuffer(), &gui.display, gui.display.scale);
+        let size = (image.width() as usize, image.height() as usize);
+        let image = ColorImage::from_rgb(size.into(), image);
+        let texture = ctx.load_texture("LCD", image, egui::TextureOptions::default());
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Display");
+            ui.label(format!("Speed: {:.0}%", gui.pacer.measured_speed() * 100.0));
+            if let Some(status) = debugger.input.status() {
+                ui.label(status);
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Screenshot").on_hover_text("F12").clicked() {
+                    gui.screenshots.take(jameboy, &gui.display);
+                }
+                let record = match gui.screenshots.recording() {
+                    true => "⏹ Stop recording",
+                    false => "⏺ Record GIF",
+                };
+                if ui.button(record).on_hover_text("F10").clicked() {
+                    gui.screenshots.toggle_recording(&gui.display);
+                }
+                ui.checkbox(&mut gui.screenshots.native, "Native size");
+                ui.label(&gui.screenshots.status);
+            });
+            ui.label(format!(
+                "Rewind: {:.1}s",
+                (debugger.rewind.len() * REWIND_INTERVAL) as f64 / jameboy::FRAME_RATE
+            ));
+            ui.image(&texture);
+        });
+    });
+}
+
+/// Tells the user about anything that happened on the link cable since the last call.
+fn report_link_events(jameboy: &mut Jameboy) {
+    while let Some(event) = jameboy.take_link_event() {
+        match event {
+            LinkEvent::Disconnected(Some(e)) => eprintln!("error: link cable disconnected: {}", e),
+            LinkEvent::Disconnected(None) => eprintln!("error: link cable disconnected"),
+            LinkEvent::Printed(path) => println!("Printed {}", path.display()),
+            LinkEvent::PrintFailed(path, e) => {
+                eprintln!("error: could not save print to {}: {}", path.display(), e)
+            }
+            LinkEvent::UnknownPrinterCommand(command) => {
+                eprintln!("error: unknown printer command {:#04x}", command)
+            }
+        }
+    }
+}
+
+fn open_link(args: &LinkArgs, save_dir: &Path) -> Result<Option<Box<dyn SerialLink>>, String> {
+    if let Some(address) = &args.link_listen {
+        println!("Waiting for link cable connection on {}", address);
+        let link = TcpLink::listen(address.as_str())
+            .map_err(|e| format!("could not listen for link cable on {}: {}", address, e))?;
+        return Ok(Some(Box::new(link)));
+    }
+
+    if let Some(address) = &args.link_connect {
+        let link = TcpLink::connect(address.as_str())
+            .map_err(|e| format!("could not connect link cable to {}: {}", address, e))?;
+        return Ok(Some(Box::new(link)));
+    }
+
+    if args.printer {
+        return Ok(Some(Box::new(Printer::init(save_dir.to_path_buf()))));
+    }
+
+    Ok(None)
+}
+
+/// Name used for the files saved for a ROM, such as save states and screenshots.
+fn rom_name(rom_path: &Path) -> String {
+    rom_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "jameboy".to_string())
+}
+
+fn open_rom(rom_path: &Path) -> Result<ROM, String> {
+    let mut rom_file =
+        File::open(rom_path).map_err(|e| format!("could not open {}: {}", rom_path.display(), e))?;
+    let mut rom = Vec::new();
+    rom_file
+        .read_to_end(&mut rom)
+        .map_err(|e| format!("could not read {}: {}", rom_path.display(), e))?;
+
+    Ok(rom)
+}
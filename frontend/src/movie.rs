@@ -0,0 +1,95 @@
+use jameboy::joypad::Buttons;
+use jameboy::movie::Movie;
+use std::fs;
+use std::path::PathBuf;
+
+enum Mode {
+    Live,
+    Recording { movie: Movie, path: PathBuf },
+    Playing { movie: Movie },
+}
+
+/// Decides the joypad input for each frame, from the keyboard or a movie being played back.
+///
+/// Input only changes when a frame starts so that a movie, which stores one input per frame,
+/// replays exactly what was recorded.
+pub struct MovieInput {
+    mode: Mode,
+    held: Buttons,
+    frame: Option<usize>,
+}
+
+impl MovieInput {
+    pub fn live() -> Self {
+        Self::init(Mode::Live)
+    }
+
+    pub fn record(movie: Movie, path: PathBuf) -> Self {
+        Self::init(Mode::Recording { movie, path })
+    }
+
+    pub fn play(movie: Movie) -> Self {
+        Self::init(Mode::Playing { movie })
+    }
+
+    fn init(mode: Mode) -> Self {
+        Self {
+            mode,
+            held: Buttons::default(),
+            frame: None,
+        }
+    }
+
+    /// Sets the buttons currently held on the keyboard, used from the start of the next frame.
+    pub fn set_held(&mut self, buttons: Buttons) {
+        self.held = buttons;
+    }
+
+    /// Returns the input to apply if `frame` has just started. Call before every step.
+    pub fn frame_input(&mut self, frame: usize) -> Option<Buttons> {
+        if self.frame == Some(frame) {
+            return None;
+        }
+        self.frame = Some(frame);
+
+        Some(match &mut self.mode {
+            Mode::Live => self.held,
+            Mode::Recording { movie, .. } => {
+                movie.record(frame, self.held);
+                self.held
+            }
+            // The keyboard takes over once the movie runs out
+            Mode::Playing { movie } => movie.input(frame).unwrap_or(self.held),
+        })
+    }
+
+    /// Number of frames in the movie being played back.
+    pub fn movie_len(&self) -> Option<usize> {
+        match &self.mode {
+            Mode::Playing { movie } => Some(movie.len()),
+            _ => None,
+        }
+    }
+
+    /// Writes out the movie being recorded, if any.
+    pub fn save(&self) -> Result<(), String> {
+        if let Mode::Recording { movie, path } = &self.mode {
+            fs::write(path, movie.save())
+                .map_err(|e| format!("could not save movie to {}: {}", path.display(), e))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn status(&self) -> Option<String> {
+        match &self.mode {
+            Mode::Live => None,
+            Mode::Recording { movie, .. } => Some(format!("Recording movie: {} frames", movie.len())),
+            Mode::Playing { movie } => Some(format!(
+                "Playing movie: frame {} of {}",
+                self.frame.unwrap_or(0).min(movie.len()),
+                movie.len()
+            )),
+        }
+    }
+}
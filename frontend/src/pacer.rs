@@ -0,0 +1,199 @@
+use jameboy::FRAME_RATE;
+
+use std::time::{Duration, Instant};
+
+// Frames to catch up on at once before giving up and letting emulation fall behind
+const MAX_FRAMES_BEHIND: u32 = 4;
+const SPEED_SAMPLE_PERIOD: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    Half,
+    Normal,
+    Double,
+    Unthrottled,
+}
+
+impl Speed {
+    pub const ALL: [Speed; 4] = [Speed::Half, Speed::Normal, Speed::Double, Speed::Unthrottled];
+
+    fn multiplier(&self) -> Option<f64> {
+        match self {
+            Self::Half => Some(0.5),
+            Self::Normal => Some(1.0),
+            Self::Double => Some(2.0),
+            Self::Unthrottled => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Half => "0.5x",
+            Self::Normal => "1x",
+            Self::Double => "2x",
+            Self::Unthrottled => "Unthrottled",
+        }
+    }
+}
+
+/// Paces emulation to the DMG refresh rate using the wall clock.
+///
+/// When the frontend falls behind, several frames are emulated before the next repaint so the
+/// ones in between are never drawn. Beyond `MAX_FRAMES_BEHIND` the backlog is dropped instead, so
+/// a slow host runs the game slowly rather than stalling to catch up.
+pub struct Pacer {
+    pub speed: Speed,
+    next_frame: Instant,
+    sampled_frames: u32,
+    sample_start: Instant,
+    measured_speed: f64,
+}
+
+fn frame_duration() -> Duration {
+    Duration::from_secs_f64(1.0 / FRAME_RATE)
+}
+
+impl Pacer {
+    pub fn init() -> Self {
+        let now = Instant::now();
+        Self {
+            speed: Speed::Normal,
+            next_frame: now,
+            sampled_frames: 0,
+            sample_start: now,
+            measured_speed: 0.0,
+        }
+    }
+
+    /// Calls `run_frame` for each frame due at the current speed, or for as many frames as fit in
+    /// one refresh period when unthrottled or `fast_forward` is held.
+    pub fn run_due_frames(&mut self, fast_forward: bool, mut run_frame: impl FnMut()) {
+        let speed = match fast_forward {
+            true => Speed::Unthrottled,
+            false => self.speed,
+        };
+
+        let frames = match speed.multiplier() {
+            Some(multiplier) => {
+                let frames = self.frames_due(FRAME_RATE * multiplier, Instant::now());
+                for _ in 0..frames {
+                    run_frame();
+                }
+                frames
+            }
+            None => {
+                let mut frames = 0;
+                let deadline = Instant::now() + frame_duration();
+                while Instant::now() < deadline {
+                    run_frame();
+                    frames += 1;
+                }
+                self.next_frame = Instant::now();
+                frames
+            }
+        };
+
+        self.record_frames(frames);
+    }
+
+    /// How many frames to run at `now` to keep up with `frame_rate`.
+    fn frames_due(&mut self, frame_rate: f64, now: Instant) -> u32 {
+        if now < self.next_frame {
+            return 0;
+        }
+
+        let frame_duration = Duration::from_secs_f64(1.0 / frame_rate);
+        let frames_behind = ((now - self.next_frame).as_secs_f64() * frame_rate) as u32 + 1;
+        if frames_behind > MAX_FRAMES_BEHIND {
+            self.next_frame = now + frame_duration;
+            return MAX_FRAMES_BEHIND;
+        }
+
+        self.next_frame += frame_duration * frames_behind;
+        frames_behind
+    }
+
+    fn record_frames(&mut self, frames: u32) {
+        self.sampled_frames += frames;
+
+        let elapsed = self.sample_start.elapsed();
+        if elapsed >= SPEED_SAMPLE_PERIOD {
+            self.measured_speed = self.sampled_frames as f64 / (elapsed.as_secs_f64() * FRAME_RATE);
+            self.sampled_frames = 0;
+            self.sample_start = Instant::now();
+        }
+    }
+
+    /// Restarts pacing from now, so time spent paused isn't caught up on.
+    pub fn pause(&mut self) {
+        let now = Instant::now();
+        self.next_frame = now;
+        self.sampled_frames = 0;
+        self.sample_start = now;
+        self.measured_speed = 0.0;
+    }
+
+    pub fn time_until_next_frame(&self) -> Duration {
+        self.next_frame.saturating_duration_since(Instant::now())
+    }
+
+    /// Emulation speed relative to real hardware over the last sample period.
+    pub fn measured_speed(&self) -> f64 {
+        self.measured_speed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A pacer whose next frame is due at the returned instant.
+    fn pacer() -> (Pacer, Instant) {
+        let pacer = Pacer::init();
+        let start = pacer.next_frame;
+        (pacer, start)
+    }
+
+    /// `frames` frame periods, plus a bit so the result doesn't sit on a frame boundary.
+    fn after(frames: f64) -> Duration {
+        frame_duration().mul_f64(frames + 0.01)
+    }
+
+    #[test]
+    fn test_steady_state() {
+        let (mut pacer, start) = pacer();
+        for frame in 0..120 {
+            assert_eq!(1, pacer.frames_due(FRAME_RATE, start + after(frame as f64)));
+            assert_eq!(0, pacer.frames_due(FRAME_RATE, start + after(frame as f64 + 0.5)));
+        }
+    }
+
+    #[test]
+    fn test_short_stall_catches_up() {
+        let (mut pacer, start) = pacer();
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, start));
+
+        // Less than a frame late still only runs the one frame, then goes back on schedule
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, start + after(1.6)));
+        assert_eq!(0, pacer.frames_due(FRAME_RATE, start + after(1.9)));
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, start + after(2.0)));
+
+        // A few frames late runs the missed ones back to back
+        assert_eq!(3, pacer.frames_due(FRAME_RATE, start + after(5.0)));
+        assert_eq!(0, pacer.frames_due(FRAME_RATE, start + after(5.5)));
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, start + after(6.0)));
+    }
+
+    #[test]
+    fn test_long_stall_drops_frames() {
+        let (mut pacer, start) = pacer();
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, start));
+
+        let resumed = start + Duration::from_secs(2);
+        assert_eq!(MAX_FRAMES_BEHIND, pacer.frames_due(FRAME_RATE, resumed));
+        // The rest of the backlog is dropped rather than replayed
+        assert_eq!(0, pacer.frames_due(FRAME_RATE, resumed + after(0.5)));
+        assert_eq!(1, pacer.frames_due(FRAME_RATE, resumed + after(1.0)));
+        assert_eq!(0, pacer.frames_due(FRAME_RATE, resumed + after(1.5)));
+    }
+}
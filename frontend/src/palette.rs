@@ -0,0 +1,33 @@
+use clap::ValueEnum;
+use image::{GrayImage, Rgb, RgbImage};
+use jameboy::ppu::PixelColour;
+
+/// Colours the four DMG shades are displayed with.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Palette {
+    /// Neutral grays
+    Gray,
+    /// The green tint of the original DMG screen
+    Dmg,
+    /// The Game Boy Pocket's higher contrast screen
+    Pocket,
+}
+
+impl Palette {
+    fn colour(&self, pixel_colour: PixelColour) -> Rgb<u8> {
+        let colours = match self {
+            Self::Gray => [[255, 255, 255], [211, 211, 211], [169, 169, 169], [0, 0, 0]],
+            Self::Dmg => [[155, 188, 15], [139, 172, 15], [48, 98, 48], [15, 56, 15]],
+            Self::Pocket => [[196, 207, 161], [139, 149, 109], [77, 83, 60], [31, 31, 31]],
+        };
+
+        Rgb(colours[pixel_colour as usize])
+    }
+
+    /// Colours a frame rendered by the PPU.
+    pub fn apply(&self, image: &GrayImage) -> RgbImage {
+        RgbImage::from_fn(image.width(), image.height(), |x, y| {
+            self.colour(PixelColour::from_grayscale(*image.get_pixel(x, y)))
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use jameboy::Jameboy;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const SLOTS: usize = 9;
+
+const KEY_SAVE: egui::Key = egui::Key::F5;
+const KEY_LOAD: egui::Key = egui::Key::F8;
+const KEY_SLOTS: [egui::Key; SLOTS] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// Numbered save state slots, stored next to each other as `<rom name>.ss<slot>` in the save directory.
+pub struct SaveStates {
+    directory: PathBuf,
+    rom_name: String,
+    pub slot: usize,
+    pub status: String,
+}
+
+impl SaveStates {
+    pub fn init(save_dir: &Path, rom_name: &str) -> Self {
+        Self {
+            directory: save_dir.to_path_buf(),
+            rom_name: rom_name.to_string(),
+            slot: 1,
+            status: String::new(),
+        }
+    }
+
+    /// Handles the slot selection (1-9), save (F5) and load (F8) hotkeys.
+    /// Returns true if a state was loaded.
+    pub fn handle_hotkeys(&mut self, ctx: &egui::Context, jameboy: &mut Jameboy) -> bool {
+        let (selected_slot, save, load) = ctx.input(|i| {
+            (
+                KEY_SLOTS.iter().position(|key| i.key_pressed(*key)),
+                i.key_pressed(KEY_SAVE),
+                i.key_pressed(KEY_LOAD),
+            )
+        });
+
+        if let Some(index) = selected_slot {
+            self.slot = index + 1;
+            self.status = format!("Selected slot {}", self.slot);
+        }
+        if save {
+            self.save(jameboy);
+        }
+        load && self.load(jameboy)
+    }
+
+    pub fn save(&mut self, jameboy: &Jameboy) {
+        let path = self.path();
+        self.status = match fs::write(&path, jameboy.save_state()) {
+            Ok(()) => format!("Saved slot {}", self.slot),
+            Err(e) => format!("Could not save {}: {}", path.display(), e),
+        };
+    }
+
+    /// Returns true if the state was loaded.
+    pub fn load(&mut self, jameboy: &mut Jameboy) -> bool {
+        let path = self.path();
+        let result = fs::read(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))
+            .and_then(|data| {
+                jameboy
+                    .load_state(&data)
+                    .map_err(|e| format!("Could not load {}: {}", path.display(), e))
+            });
+
+        match result {
+            Ok(()) => {
+                self.status = format!("Loaded slot {}", self.slot);
+                true
+            }
+            Err(e) => {
+                self.status = e;
+                false
+            }
+        }
+    }
+
+    pub fn exists(&self, slot: usize) -> bool {
+        self.slot_path(slot).exists()
+    }
+
+    fn path(&self) -> PathBuf {
+        self.slot_path(self.slot)
+    }
+
+    fn slot_path(&self, slot: usize) -> PathBuf {
+        self.directory.join(format!("{}.ss{}", self.rom_name, slot))
+    }
+}
@@ -0,0 +1,114 @@
+use crate::cli::DisplayArgs;
+use crate::video::VideoRecorder;
+
+use image::{GrayImage, RgbImage};
+use jameboy::Jameboy;
+use std::path::{Path, PathBuf};
+
+const KEY_SCREENSHOT: egui::Key = egui::Key::F12;
+const KEY_RECORD: egui::Key = egui::Key::F10;
+
+/// Colours a frame with the display palette and scales it up by `scale`.
+pub fn render(framebuffer: &GrayImage, display: &DisplayArgs, scale: u32) -> RgbImage {
+    let image = display.palette.apply(framebuffer);
+    image::imageops::resize(
+        &image,
+        image.width() * scale,
+        image.height() * scale,
+        image::imageops::FilterType::Nearest,
+    )
+}
+
+pub fn save(framebuffer: &GrayImage, display: &DisplayArgs, scale: u32, path: &Path) -> Result<(), String> {
+    render(framebuffer, display, scale)
+        .save(path)
+        .map_err(|e| format!("could not save screenshot to {}: {}", path.display(), e))
+}
+
+/// Screenshots and GIF recordings taken from the GUI, numbered as `<rom name>-<n>.png` or
+/// `<rom name>-<n>.gif` in the save directory.
+pub struct Screenshots {
+    directory: PathBuf,
+    rom_name: String,
+    /// Save at 160x144 rather than the display scale
+    pub native: bool,
+    pub status: String,
+    recording: Option<VideoRecorder>,
+}
+
+impl Screenshots {
+    pub fn init(save_dir: &Path, rom_name: &str) -> Self {
+        Self {
+            directory: save_dir.to_path_buf(),
+            rom_name: rom_name.to_string(),
+            native: false,
+            status: String::new(),
+            recording: None,
+        }
+    }
+
+    /// Handles the screenshot (F12) and recording (F10) hotkeys.
+    pub fn handle_hotkeys(&mut self, ctx: &egui::Context, jameboy: &Jameboy, display: &DisplayArgs) {
+        let (screenshot, record) =
+            ctx.input(|i| (i.key_pressed(KEY_SCREENSHOT), i.key_pressed(KEY_RECORD)));
+        if screenshot {
+            self.take(jameboy, display);
+        }
+        if record {
+            self.toggle_recording(display);
+        }
+    }
+
+    pub fn take(&mut self, jameboy: &Jameboy, display: &DisplayArgs) {
+        let path = self.next_path("png");
+        self.status = match save(jameboy.framebuffer(), display, self.scale(display), &path) {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(e) => e,
+        };
+    }
+
+    pub fn recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Starts recording from the next frame, or finishes the current recording.
+    pub fn toggle_recording(&mut self, display: &DisplayArgs) {
+        if let Some(recording) = self.recording.take() {
+            self.status = format!("Saved {} frames to {}", recording.frames(), recording.path().display());
+            return;
+        }
+
+        let path = self.next_path("gif");
+        match VideoRecorder::start(&path, display, self.scale(display)) {
+            Ok(recording) => {
+                self.status = format!("Recording to {}", path.display());
+                self.recording = Some(recording);
+            }
+            Err(e) => self.status = e,
+        }
+    }
+
+    /// Adds the latest frame to the recording, if there is one. Call after running each frame.
+    pub fn capture(&mut self, jameboy: &Jameboy) {
+        if let Some(recording) = &mut self.recording {
+            if let Err(e) = recording.capture(jameboy) {
+                self.status = e;
+                self.recording = None;
+            }
+        }
+    }
+
+    fn scale(&self, display: &DisplayArgs) -> u32 {
+        match self.native {
+            true => 1,
+            false => display.scale,
+        }
+    }
+
+    fn next_path(&self, extension: &str) -> PathBuf {
+        (1..)
+            .map(|n| self.directory.join(format!("{}-{}.{}", self.rom_name, n, extension)))
+            .find(|path| !path.exists())
+            .expect("There's always an unused file name")
+    }
+}
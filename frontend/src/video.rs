@@ -0,0 +1,110 @@
+use crate::cli::DisplayArgs;
+use crate::screenshot;
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame};
+use jameboy::ppu::DOTS_PER_FRAME;
+use jameboy::{Jameboy, CLOCK_RATE};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+// GIF delays are in hundredths of a second and browsers slow anything under 2 down to 10, so frames
+// are shown for 2 and played back at 50 fps rather than the DMG's 59.7
+const FRAME_DELAY_MS: u32 = 20;
+
+/// Whether frame `index` since recording started is written out. About one frame in six is dropped
+/// so the recording plays at the hardware's speed: a frame is kept when it's the first one drawn
+/// in its `FRAME_DELAY_MS` slot.
+fn keep_frame(index: usize) -> bool {
+    // Dots per slot, times 1000 to stay exact
+    let slot_length = CLOCK_RATE * FRAME_DELAY_MS as usize;
+    let slot = |frame: usize| frame * DOTS_PER_FRAME * 1000 / slot_length;
+    index == 0 || slot(index) != slot(index - 1)
+}
+
+/// Records every frame the PPU draws to an animated GIF.
+pub struct VideoRecorder {
+    encoder: GifEncoder<BufWriter<File>>,
+    path: PathBuf,
+    display: DisplayArgs,
+    scale: u32,
+    last_frame: Option<usize>,
+    captured: usize,
+    frames: usize,
+}
+
+impl VideoRecorder {
+    pub fn start(path: &Path, display: &DisplayArgs, scale: u32) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("could not create recording {}: {}", path.display(), e))?;
+        let mut encoder = GifEncoder::new(BufWriter::new(file));
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| format!("could not start recording {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            encoder,
+            path: path.to_path_buf(),
+            display: display.clone(),
+            scale,
+            last_frame: None,
+            captured: 0,
+            frames: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Adds the frame the PPU last finished, unless it was already captured or is dropped to keep
+    /// the recording at the hardware's speed, see `keep_frame`.
+    pub fn capture(&mut self, jameboy: &Jameboy) -> Result<(), String> {
+        let frame_count = jameboy.ppu.frame_count;
+        if self.last_frame == Some(frame_count) {
+            return Ok(());
+        }
+        self.last_frame = Some(frame_count);
+        self.captured += 1;
+        if !keep_frame(self.captured - 1) {
+            return Ok(());
+        }
+
+        let image = screenshot::render(jameboy.framebuffer(), &self.display, self.scale);
+        let image = DynamicImage::ImageRgb8(image).into_rgba8();
+        let frame = Frame::from_parts(image, 0, 0, Delay::from_numer_denom_ms(FRAME_DELAY_MS, 1));
+        self.encoder
+            .encode_frame(frame)
+            .map_err(|e| format!("could not write recording {}: {}", self.path.display(), e))?;
+        self.frames += 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_frame() {
+        let kept: Vec<usize> = (0..12).filter(|&index| keep_frame(index)).collect();
+        assert_eq!(vec![0, 2, 3, 4, 5, 6, 8, 9, 10, 11], kept);
+
+        // Over a minute of frames, the kept ones play back within a frame of real time
+        let frames = 60 * jameboy::FRAME_RATE as usize;
+        let kept = (0..frames).filter(|&index| keep_frame(index)).count();
+        let playback_ms = kept * FRAME_DELAY_MS as usize;
+        let real_ms = frames * DOTS_PER_FRAME * 1000 / CLOCK_RATE;
+        let off_by = playback_ms.abs_diff(real_ms);
+        assert!(off_by <= FRAME_DELAY_MS as usize, "{} vs {} ms", playback_ms, real_ms);
+
+        // Never more than one frame in a row is dropped
+        assert!((1..frames).all(|index| keep_frame(index) || keep_frame(index - 1)));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::cpu::{Cpu, Register, RegisterWide, WriteFlags};
+use crate::memory::{Address, Memory};
+
+const ADDRESS_HEADER_CHECKSUM: u16 = 0x014D;
+const ADDRESS_SC: u16 = 0xFF02;
+const ADDRESS_LCD_STATUS_REGISTER: u16 = 0xFF41;
+const ADDRESS_BOOTSTRAP_ROM_DISABLE: u16 = 0xFF50;
+
+// I/O registers as the DMG bootstrap ROM leaves them, from Pan Docs' "Power Up Sequence".
+// DMA is left out as writing it would start a transfer.
+const IO_REGISTERS: [(u16, u8); 38] = [
+    (0xFF00, 0xCF), // P1
+    (0xFF01, 0x00), // SB
+    (0xFF02, 0x7E), // SC
+    (0xFF04, 0xAB), // DIV
+    (0xFF05, 0x00), // TIMA
+    (0xFF06, 0x00), // TMA
+    (0xFF07, 0xF8), // TAC
+    (0xFF0F, 0xE1), // IF
+    (0xFF10, 0x80), // NR10
+    (0xFF11, 0xBF), // NR11
+    (0xFF12, 0xF3), // NR12
+    (0xFF13, 0xFF), // NR13
+    (0xFF14, 0xBF), // NR14
+    (0xFF16, 0x3F), // NR21
+    (0xFF17, 0x00), // NR22
+    (0xFF18, 0xFF), // NR23
+    (0xFF19, 0xBF), // NR24
+    (0xFF1A, 0x7F), // NR30
+    (0xFF1B, 0xFF), // NR31
+    (0xFF1C, 0x9F), // NR32
+    (0xFF1D, 0xFF), // NR33
+    (0xFF1E, 0xBF), // NR34
+    (0xFF20, 0xFF), // NR41
+    (0xFF21, 0x00), // NR42
+    (0xFF22, 0x00), // NR43
+    (0xFF23, 0xBF), // NR44
+    (0xFF24, 0x77), // NR50
+    (0xFF25, 0xF3), // NR51
+    (0xFF26, 0xF1), // NR52
+    (0xFF40, 0x91), // LCDC
+    (0xFF42, 0x00), // SCY
+    (0xFF43, 0x00), // SCX
+    (0xFF44, 0x00), // LY
+    (0xFF45, 0x00), // LYC
+    (0xFF47, 0xFC), // BGP
+    (0xFF48, 0xFF), // OBP0
+    (0xFF49, 0xFF), // OBP1
+    (0xFFFF, 0x00), // IE
+];
+
+/// Hardware revision, which decides the state the bootstrap ROM hands over to the cartridge in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Model {
+    /// Original Game Boy
+    Dmg,
+    /// Game Boy Pocket
+    Mgb,
+    /// Game Boy Color
+    Cgb,
+}
+
+/// Puts the CPU and I/O registers into the state the bootstrap ROM of `model` would leave them in,
+/// then unmaps the bootstrap ROM. The cartridge ROM should already be mapped.
+pub fn skip_bootstrap_rom(cpu: &mut Cpu, memory: &mut Memory, model: Model) {
+    // The DMG and MGB bootstrap ROMs leave H and C set unless the header checksum is zero
+    let header_checksum_nonzero = memory.read(Address(ADDRESS_HEADER_CHECKSUM)) != 0;
+
+    let (a, b, c, d, e, h, l) = match model {
+        Model::Dmg => (0x01, 0x00, 0x13, 0x00, 0xD8, 0x01, 0x4D),
+        Model::Mgb => (0xFF, 0x00, 0x13, 0x00, 0xD8, 0x01, 0x4D),
+        Model::Cgb => (0x11, 0x00, 0x00, 0xFF, 0x56, 0x00, 0x0D),
+    };
+    cpu.write_register(Register::A, a);
+    cpu.write_register(Register::B, b);
+    cpu.write_register(Register::C, c);
+    cpu.write_register(Register::D, d);
+    cpu.write_register(Register::E, e);
+    cpu.write_register(Register::H, h);
+    cpu.write_register(Register::L, l);
+
+    let half_carry_and_carry = match model {
+        Model::Dmg | Model::Mgb => header_checksum_nonzero,
+        Model::Cgb => false,
+    };
+    cpu.write_flags(WriteFlags {
+        zero: Some(true),
+        subtract: Some(false),
+        half_carry: Some(half_carry_and_carry),
+        carry: Some(half_carry_and_carry),
+    });
+    cpu.write_register_wide(RegisterWide::SP, 0xFFFE);
+    cpu.write_register_wide(RegisterWide::PC, 0x0100);
+    cpu.request_ime_disable();
+
+    for (address, value) in IO_REGISTERS {
+        memory.write(Address(address), value);
+    }
+    if let Model::Cgb = model {
+        memory.write(Address(ADDRESS_SC), 0x7F);
+    }
+
+    // Pan Docs lists STAT as $85: the tail of line 153, where LY already reads 0 but the PPU is
+    // still in VBlank. The PPU doesn't model that quirk, so start at the top of line 0 in OAM scan
+    // with the LY=LYC coincidence flag set instead.
+    memory.write(Address(ADDRESS_LCD_STATUS_REGISTER), 0x86);
+
+    memory.write(Address(ADDRESS_BOOTSTRAP_ROM_DISABLE), 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_bootstrap_rom() {
+        let mut cpu = Cpu::init();
+        let mut memory = Memory::init();
+        memory.write(Address(ADDRESS_HEADER_CHECKSUM), 0xE7);
+
+        skip_bootstrap_rom(&mut cpu, &mut memory, Model::Dmg);
+
+        assert_eq!(0x01B0, cpu.read_register_wide(RegisterWide::AF));
+        assert_eq!(0x0013, cpu.read_register_wide(RegisterWide::BC));
+        assert_eq!(0x00D8, cpu.read_register_wide(RegisterWide::DE));
+        assert_eq!(0x014D, cpu.read_register_wide(RegisterWide::HL));
+        assert_eq!(0xFFFE, cpu.read_register_wide(RegisterWide::SP));
+        assert_eq!(0x0100, cpu.read_register_wide(RegisterWide::PC));
+        assert_eq!(0x91, memory.read(Address(0xFF40)));
+
+        let mut cpu = Cpu::init();
+        memory.write(Address(ADDRESS_HEADER_CHECKSUM), 0x00);
+        skip_bootstrap_rom(&mut cpu, &mut memory, Model::Mgb);
+        assert_eq!(0xFF80, cpu.read_register_wide(RegisterWide::AF));
+    }
+}
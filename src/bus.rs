@@ -0,0 +1,158 @@
+use crate::memory::{Address, Memory};
+use crate::ppu::Ppu;
+use crate::serial::Serial;
+
+const ADDRESS_LY: u16 = 0xFF44;
+const DOTS_PER_M_CYCLE: usize = 4;
+
+/// Memory as the CPU sees it. Every access takes an M-cycle, during which the rest of the machine
+/// moves on, so the order and timing of an instruction's accesses is observable.
+pub trait Bus {
+    fn read(&mut self, address: Address) -> u8;
+    fn write(&mut self, address: Address, value: u8);
+    /// Spends an M-cycle without accessing memory.
+    fn tick(&mut self);
+    /// Reads without spending an M-cycle, for registers the CPU checks internally like IE and IF.
+    fn peek(&self, address: Address) -> u8;
+    /// Writes without spending an M-cycle, see `peek`.
+    fn poke(&mut self, address: Address, value: u8);
+}
+
+/// The bus of a whole machine, which steps OAM DMA, the serial port and the PPU on every M-cycle.
+pub struct SystemBus<'a> {
+    memory: &'a mut Memory,
+    ppu: &'a mut Ppu,
+    serial: &'a mut Serial,
+    ly_stub: Option<u8>,
+    cycles: usize,
+}
+
+impl<'a> SystemBus<'a> {
+    /// `ly_stub` is what the CPU reads from LY instead of the real value, see `Jameboy::set_ly_stub`.
+    pub fn init(memory: &'a mut Memory, ppu: &'a mut Ppu, serial: &'a mut Serial, ly_stub: Option<u8>) -> Self {
+        Self {
+            memory,
+            ppu,
+            serial,
+            ly_stub,
+            cycles: 0,
+        }
+    }
+
+    /// M-cycles spent so far.
+    pub fn cycles(&self) -> usize {
+        self.cycles
+    }
+}
+
+impl Bus for SystemBus<'_> {
+    fn read(&mut self, address: Address) -> u8 {
+        self.tick();
+        match self.ly_stub {
+            Some(ly) if address.0 == ADDRESS_LY => ly,
+            _ => self.memory.read(address),
+        }
+    }
+
+    fn write(&mut self, address: Address, value: u8) {
+        self.tick();
+        self.memory.write(address, value);
+    }
+
+    fn tick(&mut self) {
+        self.memory.step();
+        self.serial.step(self.memory);
+        for _ in 0..DOTS_PER_M_CYCLE {
+            self.ppu.step(self.memory);
+        }
+        self.cycles += 1;
+    }
+
+    fn peek(&self, address: Address) -> u8 {
+        self.memory.read(address)
+    }
+
+    fn poke(&mut self, address: Address, value: u8) {
+        self.memory.write(address, value);
+    }
+}
+
+/// What the CPU did with the bus during an M-cycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read { address: u16, value: u8 },
+    Write { address: u16, value: u8 },
+    Internal,
+}
+
+/// Plain memory that counts M-cycles and records what each was spent on, for testing the CPU on
+/// its own.
+pub struct TestBus {
+    pub memory: Memory,
+    pub cycles: usize,
+    pub accesses: Vec<Access>,
+}
+
+impl TestBus {
+    pub fn init(memory: Memory) -> Self {
+        Self {
+            memory,
+            cycles: 0,
+            accesses: Vec::new(),
+        }
+    }
+}
+
+impl Bus for TestBus {
+    fn read(&mut self, address: Address) -> u8 {
+        let raw_address = address.0;
+        let value = self.memory.read(address);
+        self.cycles += 1;
+        self.accesses.push(Access::Read { address: raw_address, value });
+        value
+    }
+
+    fn write(&mut self, address: Address, value: u8) {
+        self.cycles += 1;
+        self.accesses.push(Access::Write { address: address.0, value });
+        self.memory.write(address, value);
+    }
+
+    fn tick(&mut self) {
+        self.cycles += 1;
+        self.accesses.push(Access::Internal);
+    }
+
+    fn peek(&self, address: Address) -> u8 {
+        self.memory.read(address)
+    }
+
+    fn poke(&mut self, address: Address, value: u8) {
+        self.memory.write(address, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_bus_records_accesses() {
+        let mut bus = TestBus::init(Memory::init());
+        bus.write(Address(0xC000), 0x12);
+        bus.tick();
+        assert_eq!(0x12, bus.read(Address(0xC000)));
+        bus.poke(Address(0xC001), 0x34);
+        assert_eq!(0x34, bus.peek(Address(0xC001)));
+
+        assert_eq!(3, bus.cycles);
+        assert_eq!(
+            vec![
+                Access::Write { address: 0xC000, value: 0x12 },
+                Access::Internal,
+                Access::Read { address: 0xC000, value: 0x12 },
+            ],
+            bus.accesses
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use crate::util;
-use crate::memory::{ Address, Memory };
+use crate::bus::Bus;
+use crate::memory::Address;
 use crate::opcode;
-use crate::util::{ bit, u16_to_u8, set_bits };
+use crate::state::{StateError, StateReader, StateWriter};
+use crate::util::{ u16_to_u8, set_bits };
 
 const ADDRESS_INTERRUPT_FLAG_REGISTER: u16 = 0xFF0F;
 const ADDRESS_INTERRUPT_ENABLE_REGISTER: u16 = 0xFFFF;
@@ -10,6 +12,14 @@ const ADDRESS_LCD_INTERRUPT: u16 = 0x48;
 const ADDRESS_TIMER_INTERRUPT: u16 = 0x50;
 const ADDRESS_SERIAL_INTERRUPT: u16 = 0x58;
 const ADDRESS_JOYPAD_INTERRUPT: u16 = 0x60;
+/// Handlers of the interrupts in IE and IF, highest priority first.
+const INTERRUPT_HANDLERS: [u16; 5] = [
+    ADDRESS_VBLANK_INTERRUPT,
+    ADDRESS_LCD_INTERRUPT,
+    ADDRESS_TIMER_INTERRUPT,
+    ADDRESS_SERIAL_INTERRUPT,
+    ADDRESS_JOYPAD_INTERRUPT,
+];
 
 #[derive(Clone, Copy)]
 pub enum Register {
@@ -152,14 +162,15 @@ impl Cpu {
         self.steps_since_request_ime_enable = None;
     }
 
-    pub fn step(&mut self, memory: &mut Memory) {
-        if self.handled_interrupts(memory) {
+    /// Executes an instruction, or dispatches an interrupt, ticking the bus for every M-cycle.
+    pub fn step(&mut self, bus: &mut dyn Bus) {
+        if self.handled_interrupts(bus) {
             self.check_interrupts_enabled();
             return;
         }
 
         let pc = self.read_register_wide(RegisterWide::PC);
-        let byte = memory.read(Address(pc));
+        let byte = bus.read(Address(pc));
         let opcode = if self.prefixed {
             self.prefixed = false;
             opcode::decode_prefixed(byte)
@@ -172,11 +183,9 @@ impl Cpu {
         }
 
         let opcode = opcode.unwrap();
-        if opcode.mnemonic == "PREFIX" {
-            self.prefixed = true;
-        }
+        self.prefixed = opcode.prefix;
         self.pc += opcode.size_bytes as u16;
-        opcode.execute(self, memory);
+        opcode.execute(self, bus);
         self.check_interrupts_enabled();
     }
 
@@ -193,43 +202,76 @@ impl Cpu {
         }
     }
 
-    fn handled_interrupts(&mut self, memory: &mut Memory) -> bool {
-        if !self.ime {
+    /// Dispatches the highest priority pending interrupt, if IME is set. Dispatch takes 5 M-cycles:
+    /// two idle, two pushing PC and one jumping to the handler.
+    fn handled_interrupts(&mut self, bus: &mut dyn Bus) -> bool {
+        // A CB prefixed opcode can't be interrupted between its two bytes
+        if !self.ime || self.prefixed || pending_interrupts(bus) == 0 {
             return false;
         }
 
-        let ie_register = memory.read(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER));
-        let if_register = memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER));
-
-        let (bit_to_reset, interrupt_handler_address) = if bit(ie_register, 0) & bit(if_register, 0) == 1 {
-            (0, ADDRESS_VBLANK_INTERRUPT)
-        } else if bit(ie_register, 1) & bit(if_register, 1) == 1 {
-            (1, ADDRESS_LCD_INTERRUPT)
-        } else if bit(ie_register, 2) & bit(if_register, 2) == 1 {
-            (2, ADDRESS_TIMER_INTERRUPT)
-        } else if bit(ie_register, 3) & bit(if_register, 3) == 1 {
-            (3, ADDRESS_SERIAL_INTERRUPT)
-        } else if bit(ie_register, 4) & bit(if_register, 4) == 1 {
-            (4, ADDRESS_JOYPAD_INTERRUPT)
-        } else {
-            return false;
+        self.ime = false;
+        bus.tick();
+        bus.tick();
+
+        let (msb, lsb) = u16_to_u8(self.pc);
+        self.sp = self.sp.wrapping_sub(1);
+        bus.write(Address(self.sp), msb);
+        // The handler is picked after the high byte is pushed. If that overwrote IE and disabled
+        // every pending interrupt, the dispatch is cancelled and jumps to 0x0000 instead.
+        let pending = pending_interrupts(bus);
+        self.sp = self.sp.wrapping_sub(1);
+        bus.write(Address(self.sp), lsb);
+
+        bus.tick();
+        self.pc = match pending.trailing_zeros() as usize {
+            interrupt if interrupt < INTERRUPT_HANDLERS.len() => {
+                // The corresponding bit in IF is reset once the interrupt is dispatched
+                let if_register = bus.peek(Address(ADDRESS_INTERRUPT_FLAG_REGISTER));
+                bus.poke(Address(ADDRESS_INTERRUPT_FLAG_REGISTER), set_bits(if_register, 0, 1 << interrupt));
+                INTERRUPT_HANDLERS[interrupt]
+            }
+            _ => 0x0000,
         };
 
-        // When an interrupt is executed, the corresponding bit in the IF register becomes automatically reset 
-        // by the CPU, and the IME flag becomes cleared.
-        self.ime = false;
-        memory.write(Address(ADDRESS_INTERRUPT_FLAG_REGISTER), set_bits(if_register, 0, 1 << bit_to_reset));
+        true
+    }
 
-        let pc = self.read_register_wide(RegisterWide::PC);
-        let sp = self.read_register_wide(RegisterWide::SP);
-        let new_sp = sp - 2;
-        let (msb, lsb) = u16_to_u8(pc);
-        memory.write(Address(new_sp), lsb);
-        memory.write(Address(new_sp + 1), msb);
-        self.write_register_wide(RegisterWide::SP, new_sp);
-        self.write_register_wide(RegisterWide::PC, interrupt_handler_address);
-
-        return true;
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        for register in [self.a, self.b, self.c, self.d, self.e, self.f, self.h, self.l] {
+            writer.u8(register);
+        }
+        writer.u16(self.pc);
+        writer.u16(self.sp);
+        writer.bool(self.ime);
+        writer.bool(self.steps_since_request_ime_enable.is_some());
+        writer.u8(self.steps_since_request_ime_enable.unwrap_or(0));
+        writer.bool(self.prefixed);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        for register in [
+            &mut self.a,
+            &mut self.b,
+            &mut self.c,
+            &mut self.d,
+            &mut self.e,
+            &mut self.f,
+            &mut self.h,
+            &mut self.l,
+        ] {
+            *register = reader.u8()?;
+        }
+        self.pc = reader.u16()?;
+        self.sp = reader.u16()?;
+        self.ime = reader.bool()?;
+        let ime_enable_requested = reader.bool()?;
+        let steps_since_request_ime_enable = reader.u8()?;
+        self.steps_since_request_ime_enable =
+            ime_enable_requested.then_some(steps_since_request_ime_enable);
+        self.prefixed = reader.bool()?;
+
+        Ok(())
     }
 
     pub fn write_flags(&mut self, f: WriteFlags) {
@@ -259,9 +301,65 @@ impl Cpu {
     }
 }
 
+/// Interrupts that are both requested in IF and enabled in IE.
+fn pending_interrupts(bus: &dyn Bus) -> u8 {
+    let ie_register = bus.peek(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER));
+    let if_register = bus.peek(Address(ADDRESS_INTERRUPT_FLAG_REGISTER));
+    ie_register & if_register & 0b0001_1111
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bus::TestBus;
+    use crate::memory::Memory;
+
+    /// A CPU at `pc` with IME set and a bus with `ie` and `if_register` set.
+    fn init_interrupt(pc: u16, sp: u16, ie: u8, if_register: u8) -> (Cpu, TestBus) {
+        let mut cpu = Cpu::init();
+        cpu.pc = pc;
+        cpu.sp = sp;
+
+        let mut memory = Memory::init();
+        memory.write(Address(0xFF50), 1);
+        memory.write(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER), ie);
+        memory.write(Address(ADDRESS_INTERRUPT_FLAG_REGISTER), if_register);
+
+        (cpu, TestBus::init(memory))
+    }
+
+    #[test]
+    fn test_interrupt_dispatch() {
+        // Timer and serial requested, only timer and joypad enabled
+        let (mut cpu, mut bus) = init_interrupt(0x1234, 0xD000, 0b0001_0100, 0b0000_1100);
+        cpu.step(&mut bus);
+
+        assert_eq!(ADDRESS_TIMER_INTERRUPT, cpu.pc);
+        assert_eq!(0xCFFE, cpu.sp);
+        assert_eq!(0x12, bus.memory.read(Address(0xCFFF)));
+        assert_eq!(0x34, bus.memory.read(Address(0xCFFE)));
+        assert_eq!(0b1110_1000, bus.memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER)));
+        assert!(!cpu.ime);
+        assert_eq!(5, bus.cycles);
+    }
+
+    #[test]
+    fn test_interrupt_dispatch_cancelled_by_ie_push() {
+        // Pushing PC's high byte, 0x02, to IE disables the requested VBlank interrupt
+        let (mut cpu, mut bus) = init_interrupt(0x0234, 0x0000, 0b0000_0001, 0b0000_0001);
+        cpu.step(&mut bus);
+
+        assert_eq!(0x0000, cpu.pc);
+        assert_eq!(0x02, bus.memory.read(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER)));
+        assert_eq!(0b1110_0001, bus.memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER)));
+        assert_eq!(5, bus.cycles);
+
+        // While enabling LCD instead dispatches that
+        let (mut cpu, mut bus) = init_interrupt(0x0234, 0x0000, 0b0000_0001, 0b0000_0011);
+        cpu.step(&mut bus);
+        assert_eq!(ADDRESS_LCD_INTERRUPT, cpu.pc);
+        assert_eq!(0b1110_0001, bus.memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER)));
+    }
 
     #[test]
     fn test_read_flags() {
@@ -3,7 +3,7 @@ use crate::opcode;
 
 pub struct Instruction {
     pub address: Address,
-    pub opcode: Option<opcode::Opcode>,
+    pub opcode: Option<&'static opcode::Opcode>,
 }
 
 pub fn disassemble(program: &[u8]) -> Vec<Instruction> {
@@ -20,10 +20,8 @@ pub fn disassemble(program: &[u8]) -> Vec<Instruction> {
         };
 
         let mut pc_increment = 1;
-        if let Some(ref o) = opcode {
-            if o.mnemonic == "PREFIX" {
-                prefixed = true;
-            }
+        if let Some(o) = opcode {
+            prefixed = o.prefix;
             pc_increment = o.size_bytes as usize;
         }
 
@@ -1,60 +1,91 @@
 use crate::memory::{Address, Memory};
+use crate::state::{StateError, StateReader, StateWriter};
 
 const ADDRESS_JOYP_REGISTER: u16 = 0xFF00;
 
-const KEY_RIGHT: egui::Key = egui::Key::D;
-const KEY_LEFT: egui::Key = egui::Key::A;
-const KEY_UP: egui::Key = egui::Key::W;
-const KEY_DOWN: egui::Key = egui::Key::S;
-const KEY_A: egui::Key = egui::Key::J;
-const KEY_B: egui::Key = egui::Key::K;
-const KEY_SELECT: egui::Key = egui::Key::Enter;
-const KEY_START: egui::Key = egui::Key::Escape;
+/// Buttons held down on the joypad.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Buttons {
+    pub right: bool,
+    pub left: bool,
+    pub up: bool,
+    pub down: bool,
+    pub a: bool,
+    pub b: bool,
+    pub select: bool,
+    pub start: bool,
+}
 
-pub fn handle_input(ctx: &egui::Context, memory: &mut Memory) {
+pub fn write_register(memory: &mut Memory, buttons: Buttons) {
     let mut register = 0xFF;
-    
-    ctx.input(|i| {
-        if i.key_pressed(KEY_RIGHT) {
-            register &= 0b1111_1110;
-            register &= 0b1110_1111;
-        }
-
-        if i.key_pressed(KEY_LEFT) {
-            register &= 0b1111_1101;
-            register &= 0b1110_1111;
-        }
-
-        if i.key_pressed(KEY_UP) {
-            register &= 0b1111_1011;
-            register &= 0b1110_1111;
-        }
-
-        if i.key_pressed(KEY_DOWN) {
-            register &= 0b1111_0111;
-            register &= 0b1110_1111;
-        }
-
-        if i.key_pressed(KEY_A) {
-            register &= 0b1111_1110;
-            register &= 0b1110_1111;
-        }
-
-        if i.key_pressed(KEY_B) {
-            register &= 0b1111_1101;
-            register &= 0b1101_1111;
-        }
-
-        if i.key_pressed(KEY_SELECT) {
-            register &= 0b1111_1011;
-            register &= 0b1101_1111;
-        }
-
-        if i.key_pressed(KEY_START) {
-            register &= 0b1111_0111;
-            register &= 0b1101_1111;
-        }
-    });
+
+    if buttons.right {
+        register &= 0b1111_1110;
+        register &= 0b1110_1111;
+    }
+
+    if buttons.left {
+        register &= 0b1111_1101;
+        register &= 0b1110_1111;
+    }
+
+    if buttons.up {
+        register &= 0b1111_1011;
+        register &= 0b1110_1111;
+    }
+
+    if buttons.down {
+        register &= 0b1111_0111;
+        register &= 0b1110_1111;
+    }
+
+    if buttons.a {
+        register &= 0b1111_1110;
+        register &= 0b1110_1111;
+    }
+
+    if buttons.b {
+        register &= 0b1111_1101;
+        register &= 0b1101_1111;
+    }
+
+    if buttons.select {
+        register &= 0b1111_1011;
+        register &= 0b1101_1111;
+    }
+
+    if buttons.start {
+        register &= 0b1111_0111;
+        register &= 0b1101_1111;
+    }
 
     memory.write(Address(ADDRESS_JOYP_REGISTER), register);
 }
+
+pub fn save_state(writer: &mut StateWriter, buttons: Buttons) {
+    for pressed in [
+        buttons.right,
+        buttons.left,
+        buttons.up,
+        buttons.down,
+        buttons.a,
+        buttons.b,
+        buttons.select,
+        buttons.start,
+    ] {
+        writer.bool(pressed);
+    }
+}
+
+pub fn load_state(reader: &mut StateReader) -> Result<Buttons, StateError> {
+    Ok(Buttons {
+        right: reader.bool()?,
+        left: reader.bool()?,
+        up: reader.bool()?,
+        down: reader.bool()?,
+        a: reader.bool()?,
+        b: reader.bool()?,
+        select: reader.bool()?,
+        start: reader.bool()?,
+    })
+}
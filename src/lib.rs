@@ -0,0 +1,365 @@
+//! The jameboy emulator core. Frontends load a cartridge into a [`Jameboy`], step it, feed it
+//! input and read back the framebuffer; the egui frontend lives in the `frontend` crate.
+
+pub mod boot;
+pub mod bus;
+pub mod cpu;
+pub mod disassembly;
+pub mod joypad;
+pub mod link;
+pub mod memory;
+pub mod mooneye;
+pub mod movie;
+pub mod opcode;
+pub mod ppu;
+pub mod printer;
+pub mod rewind;
+pub mod serial;
+pub mod state;
+pub mod timeline;
+pub mod util;
+
+use crate::boot::Model;
+use crate::bus::SystemBus;
+use crate::cpu::Cpu;
+use crate::joypad::Buttons;
+use crate::memory::{Address, Memory};
+use crate::ppu::{Ppu, RendererKind};
+use crate::serial::{LinkEvent, Serial, SerialLink};
+use crate::state::{StateError, StateReader, StateWriter};
+
+use image::GrayImage;
+
+/// DMG master clock rate in Hz, one dot per tick.
+pub const CLOCK_RATE: usize = 4_194_304;
+/// DMG refresh rate in Hz, roughly 59.73.
+pub const FRAME_RATE: f64 = CLOCK_RATE as f64 / ppu::DOTS_PER_FRAME as f64;
+
+/// A Game Boy, independent of any frontend.
+pub struct Jameboy {
+    pub cpu: Cpu,
+    pub memory: Memory,
+    pub ppu: Ppu,
+    pub serial: Serial,
+    buttons: Buttons,
+    ly_stub: Option<u8>,
+}
+
+impl Jameboy {
+    pub fn init() -> Self {
+        Self {
+            cpu: Cpu::init(),
+            memory: Memory::init(),
+            ppu: Ppu::init(),
+            serial: Serial::init(),
+            buttons: Buttons::default(),
+            ly_stub: None,
+        }
+    }
+
+    /// Powers on with RAM filled from `seed`, see `Memory::init_with_seed`.
+    pub fn init_with_seed(seed: u64) -> Self {
+        Self {
+            memory: Memory::init_with_seed(seed),
+            ..Self::init()
+        }
+    }
+
+    pub fn load_bootstrap_rom(&mut self, rom: &[u8]) {
+        self.memory.load_bootstrap_rom(rom);
+    }
+
+    pub fn load_cartridge(&mut self, rom: &[u8]) {
+        for (i, byte) in rom.iter().enumerate() {
+            self.memory.write(Address(i as u16), *byte);
+        }
+    }
+
+    /// Starts the cartridge from the state the bootstrap ROM of `model` leaves the machine in.
+    pub fn skip_bootstrap_rom(&mut self, model: Model) {
+        boot::skip_bootstrap_rom(&mut self.cpu, &mut self.memory, model);
+    }
+
+    pub fn connect_serial(&mut self, link: Box<dyn SerialLink>) {
+        self.serial.connect(link);
+    }
+
+    /// Returns the oldest event from whatever is connected to the serial port, for the frontend to
+    /// report.
+    pub fn take_link_event(&mut self) -> Option<LinkEvent> {
+        self.serial.take_link_event()
+    }
+
+    pub fn set_input(&mut self, buttons: Buttons) {
+        self.buttons = buttons;
+    }
+
+    pub fn input(&self) -> Buttons {
+        self.buttons
+    }
+
+    /// Makes the CPU always read LY as `ly`. gameboy-doctor logs are made with LY stuck at 0x90 so
+    /// that they don't depend on PPU timing. The PPU still sees the real LY.
+    pub fn set_ly_stub(&mut self, ly: Option<u8>) {
+        self.ly_stub = ly;
+    }
+
+    /// Picks between drawing every dot as the hardware does and drawing whole scanlines at once,
+    /// see `RendererKind`.
+    pub fn set_renderer(&mut self, renderer: RendererKind) {
+        self.ppu.set_renderer(renderer);
+    }
+
+    /// The frame most recently drawn by the PPU, as 160x144 grayscale.
+    pub fn framebuffer(&self) -> &GrayImage {
+        &self.ppu.image_buffer
+    }
+
+    /// Serializes the whole machine, except for whatever is connected to the serial port.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut writer = StateWriter::init();
+        writer.chunk(*b"CPU ", |w| self.cpu.save_state(w));
+        writer.chunk(*b"MEM ", |w| self.memory.save_state(w));
+        writer.chunk(*b"PPU ", |w| self.ppu.save_state(w));
+        writer.chunk(*b"REND", |w| self.ppu.renderer().save_state(w));
+        writer.chunk(*b"DRAW", |w| self.ppu.save_renderer_state(w));
+        writer.chunk(*b"SER ", |w| self.serial.save_state(w));
+        writer.chunk(*b"JOYP", |w| joypad::save_state(w, self.buttons));
+
+        writer.finish()
+    }
+
+    /// Restores a state written by `save_state`, along with the renderer it was saved with. The
+    /// machine is left untouched if it can't be read.
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), StateError> {
+        let chunks = StateReader::chunks(data)?;
+
+        let mut cpu = Cpu::init();
+        cpu.load_state(&mut chunks.get(*b"CPU ")?)?;
+        let mut memory = Memory::init();
+        memory.load_state(&mut chunks.get(*b"MEM ")?)?;
+        // States saved before the renderer could be picked were all drawn by the FIFO renderer
+        let renderer = match chunks.get(*b"REND") {
+            Ok(mut reader) => RendererKind::load_state(&mut reader)?,
+            Err(_) => RendererKind::Fifo,
+        };
+        let mut ppu = Ppu::init_with_renderer(renderer);
+        ppu.load_state(&mut chunks.get(*b"PPU ")?, chunks.version())?;
+        if chunks.version() > 1 {
+            ppu.load_renderer_state(&mut chunks.get(*b"DRAW")?)?;
+        }
+        let buttons = joypad::load_state(&mut chunks.get(*b"JOYP")?)?;
+        let transfer_state = Serial::load_state(&mut chunks.get(*b"SER ")?)?;
+
+        self.serial.restore_transfer(transfer_state);
+        self.cpu = cpu;
+        self.memory = memory;
+        self.ppu = ppu;
+        self.buttons = buttons;
+
+        Ok(())
+    }
+
+    /// Runs until the PPU finishes drawing a frame and enters VBlank, then returns that frame.
+    pub fn run_frame(&mut self) -> &GrayImage {
+        let frame_count = self.ppu.frame_count;
+        while self.ppu.frame_count == frame_count {
+            self.step();
+        }
+
+        self.framebuffer()
+    }
+
+    /// Executes a single instruction, or dispatches an interrupt, and returns the M-cycles it took.
+    pub fn step(&mut self) -> usize {
+        joypad::write_register(&mut self.memory, self.buttons);
+
+        let mut bus = SystemBus::init(&mut self.memory, &mut self.ppu, &mut self.serial, self.ly_stub);
+        self.cpu.step(&mut bus);
+        bus.cycles()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS_LY: u16 = 0xFF44;
+
+    #[test]
+    fn test_run_frame() {
+        let mut jameboy = Jameboy::init();
+        let mut rom = vec![0; 0x8000];
+        // JR @
+        rom[0x100] = 0x18;
+        rom[0x101] = 0xFE;
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+
+        for frame in 1..=3 {
+            jameboy.run_frame();
+            assert_eq!(frame, jameboy.ppu.frame_count);
+            assert_eq!(144, jameboy.memory.read(Address(ADDRESS_LY)));
+        }
+    }
+
+    #[test]
+    fn test_ly_stub() {
+        let mut jameboy = Jameboy::init();
+        let mut rom = vec![0; 0x8000];
+        // loop: LDH A, [$44]; JR loop
+        rom[0x100..0x104].copy_from_slice(&[0xF0, 0x44, 0x18, 0xFC]);
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+        jameboy.set_ly_stub(Some(0x12));
+
+        for _ in 0..3 {
+            jameboy.run_frame();
+            assert_eq!(0x12, jameboy.cpu.read_register(cpu::Register::A));
+            assert_eq!(144, jameboy.memory.read(Address(ADDRESS_LY)));
+        }
+    }
+
+    #[test]
+    fn test_save_state_round_trip() {
+        let mut jameboy = Jameboy::init();
+        let mut rom = vec![0; 0x8000];
+        // loop: INC A; LD [HL], A; INC L; JR loop
+        rom[0x100..0x105].copy_from_slice(&[0x3C, 0x77, 0x2C, 0x18, 0xFB]);
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+        jameboy.cpu.write_register_wide(cpu::RegisterWide::HL, 0xC000);
+
+        // Stop part way through a scanline so the fetchers and FIFOs are mid-flight
+        jameboy.run_frame();
+        for _ in 0..1234 {
+            jameboy.step();
+        }
+        let state = jameboy.save_state();
+
+        let mut restored = Jameboy::init();
+        restored.load_state(&state).unwrap();
+        assert_eq!(state, restored.save_state());
+
+        for _ in 0..50_000 {
+            jameboy.step();
+            restored.step();
+        }
+        assert_eq!(jameboy.save_state(), restored.save_state());
+    }
+
+    #[test]
+    fn test_renderers_draw_the_same_frame() {
+        // (SCX, SCY), including scrolls that aren't a whole number of tiles
+        for (scx, scy) in [(0, 0), (3, 5), (8, 0), (13, 250)] {
+            let mut frames = Vec::new();
+            for renderer in [RendererKind::Fifo, RendererKind::Scanline] {
+                let mut jameboy = Jameboy::init();
+                let mut rom = vec![0; 0x8000];
+                // JR @
+                rom[0x100] = 0x18;
+                rom[0x101] = 0xFE;
+                jameboy.load_cartridge(&rom);
+                jameboy.skip_bootstrap_rom(Model::Dmg);
+                jameboy.set_renderer(renderer);
+
+                // A striped tile across the top left of the background and a checkered sprite
+                // over it, starting part way down a tile, along with the logo the bootstrap ROM
+                // leaves behind
+                for row in 0..8 {
+                    jameboy.memory.write(Address(0x8010 + 2 * row), 0xF0);
+                    jameboy.memory.write(Address(0x8011 + 2 * row), 0x3C);
+                    jameboy.memory.write(Address(0x8020 + 2 * row), 0xAA >> (row % 2));
+                }
+                for tile in 0..4 {
+                    jameboy.memory.write(Address(0x9800 + 33 * tile), 1);
+                }
+                jameboy.memory.write(Address(0xFE00), 16 + 11);
+                jameboy.memory.write(Address(0xFE01), 8 + 12);
+                jameboy.memory.write(Address(0xFE02), 2);
+                // SCX and SCY
+                jameboy.memory.write(Address(0xFF43), scx);
+                jameboy.memory.write(Address(0xFF42), scy);
+
+                jameboy.run_frame();
+                frames.push(jameboy.framebuffer().clone());
+            }
+
+            assert!(frames[0].pixels().any(|pixel| pixel.0 != [255]));
+            assert!(frames[0] == frames[1], "frames differ with SCX={} SCY={}", scx, scy);
+        }
+    }
+
+    #[test]
+    fn test_load_state_reads_version_1() {
+        let mut jameboy = Jameboy::init();
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+        jameboy.run_frame();
+        for _ in 0..1234 {
+            jameboy.step();
+        }
+
+        let state = jameboy.save_state();
+        let chunks = StateReader::chunks(&state).unwrap();
+        let mut ppu = chunks.get(*b"PPU ").unwrap();
+        let dot = ppu.u32().unwrap();
+        let frame_count = ppu.u64().unwrap();
+        let image = ppu.bytes().unwrap();
+
+        // Version 1 had the renderer's state in the middle of the PPU chunk and no DRAW chunk
+        let mut writer = StateWriter::with_header(*b"JMBS", 1);
+        writer.chunk(*b"CPU ", |w| jameboy.cpu.save_state(w));
+        writer.chunk(*b"MEM ", |w| jameboy.memory.save_state(w));
+        writer.chunk(*b"PPU ", |w| {
+            w.u32(dot);
+            jameboy.ppu.save_renderer_state(w);
+            w.u64(frame_count);
+            w.bytes(image);
+        });
+        writer.chunk(*b"SER ", |w| jameboy.serial.save_state(w));
+        writer.chunk(*b"JOYP", |w| joypad::save_state(w, jameboy.buttons));
+
+        let mut restored = Jameboy::init();
+        restored.load_state(&writer.finish()).unwrap();
+        assert_eq!(state, restored.save_state());
+    }
+
+    #[test]
+    fn test_load_state_restores_renderer() {
+        let mut jameboy = Jameboy::init();
+        jameboy.set_renderer(RendererKind::Scanline);
+        let state = jameboy.save_state();
+
+        let mut restored = Jameboy::init();
+        restored.load_state(&state).unwrap();
+        assert_eq!(RendererKind::Scanline, restored.ppu.renderer());
+    }
+
+    #[test]
+    fn test_load_state_rejects_invalid_data() {
+        let mut jameboy = Jameboy::init();
+        let state = jameboy.save_state();
+
+        assert_eq!(Err(StateError::BadMagic), jameboy.load_state(b"not a state"));
+        assert_eq!(Err(StateError::Truncated), jameboy.load_state(&state[..state.len() - 1]));
+    }
+
+    #[test]
+    fn test_load_state_is_all_or_nothing() {
+        let mut jameboy = Jameboy::init();
+        let before = jameboy.save_state();
+
+        // A serial transfer in flight, but no joypad chunk after it
+        let mut writer = StateWriter::init();
+        writer.chunk(*b"CPU ", |w| jameboy.cpu.save_state(w));
+        writer.chunk(*b"MEM ", |w| jameboy.memory.save_state(w));
+        writer.chunk(*b"PPU ", |w| jameboy.ppu.save_state(w));
+        writer.chunk(*b"SER ", |w| {
+            w.u8(1);
+            w.u32(3);
+        });
+
+        assert!(jameboy.load_state(&writer.finish()).is_err());
+        assert_eq!(before, jameboy.save_state());
+    }
+}
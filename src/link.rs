@@ -0,0 +1,219 @@
+use crate::serial::{LinkEvent, SerialLink};
+use crate::Jameboy;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::prelude::*;
+use std::io::{self, ErrorKind};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::rc::Rc;
+
+type Wire = Rc<RefCell<VecDeque<u8>>>;
+
+/// One end of a link cable between two cores in the same process.
+pub struct VirtualLink {
+    incoming: Wire,
+    outgoing: Wire,
+}
+
+/// Returns both ends of a virtual link cable.
+pub fn virtual_cable() -> (VirtualLink, VirtualLink) {
+    let left_to_right = Wire::default();
+    let right_to_left = Wire::default();
+
+    (
+        VirtualLink {
+            incoming: right_to_left.clone(),
+            outgoing: left_to_right.clone(),
+        },
+        VirtualLink {
+            incoming: left_to_right,
+            outgoing: right_to_left,
+        },
+    )
+}
+
+impl SerialLink for VirtualLink {
+    fn send(&mut self, byte: u8) {
+        self.outgoing.borrow_mut().push_back(byte);
+    }
+
+    fn receive(&mut self) -> Option<u8> {
+        self.incoming.borrow_mut().pop_front()
+    }
+}
+
+/// Two consoles connected by a virtual link cable.
+///
+/// Both cores are stepped in lockstep from a single thread, so multiplayer behaviour is
+/// deterministic and can be asserted on without sockets.
+pub struct LinkedPair {
+    pub left: Jameboy,
+    pub right: Jameboy,
+}
+
+impl LinkedPair {
+    pub fn init() -> Self {
+        let (left_link, right_link) = virtual_cable();
+        let mut left = Jameboy::init();
+        let mut right = Jameboy::init();
+        left.connect_serial(Box::new(left_link));
+        right.connect_serial(Box::new(right_link));
+
+        Self { left, right }
+    }
+
+    pub fn step(&mut self) {
+        self.left.step();
+        self.right.step();
+    }
+}
+
+/// A link cable to another jameboy process over TCP.
+pub struct TcpLink {
+    stream: Option<TcpStream>,
+    // Bytes the socket wasn't ready to take yet, sent on the next send or receive
+    outgoing: Vec<u8>,
+    events: VecDeque<LinkEvent>,
+}
+
+impl TcpLink {
+    /// Blocks until a peer connects on `address`.
+    pub fn listen<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let (stream, _) = listener.accept()?;
+        Self::from_stream(stream)
+    }
+
+    pub fn connect<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        Self::from_stream(TcpStream::connect(address)?)
+    }
+
+    fn from_stream(stream: TcpStream) -> io::Result<Self> {
+        // Every transfer is a single byte that the peer is waiting on
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+
+        Ok(Self {
+            stream: Some(stream),
+            outgoing: Vec::new(),
+            events: VecDeque::new(),
+        })
+    }
+
+    fn disconnect(&mut self, error: Option<io::Error>) {
+        self.events.push_back(LinkEvent::Disconnected(error));
+        self.stream = None;
+        self.outgoing.clear();
+    }
+
+    /// Writes as much of `outgoing` as the socket takes without blocking.
+    fn flush(&mut self) {
+        let Some(stream) = self.stream.as_mut() else {
+            return;
+        };
+
+        while !self.outgoing.is_empty() {
+            match stream.write(&self.outgoing) {
+                Ok(0) => return self.disconnect(None),
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) => return,
+                Err(e) => return self.disconnect(Some(e)),
+            }
+        }
+    }
+}
+
+impl SerialLink for TcpLink {
+    fn send(&mut self, byte: u8) {
+        if self.stream.is_some() {
+            self.outgoing.push(byte);
+            self.flush();
+        }
+    }
+
+    fn receive(&mut self) -> Option<u8> {
+        self.flush();
+        let stream = self.stream.as_mut()?;
+
+        let mut buffer = [0; 1];
+        match stream.read(&mut buffer) {
+            Ok(1) => Some(buffer[0]),
+            Ok(_) => {
+                self.disconnect(None);
+                None
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => None,
+            Err(e) => {
+                self.disconnect(Some(e));
+                None
+            }
+        }
+    }
+
+    fn connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    fn take_event(&mut self) -> Option<LinkEvent> {
+        self.events.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Address;
+
+    const ADDRESS_SB: u16 = 0xFF01;
+
+    fn load_program(jameboy: &mut Jameboy, program: &[u8]) {
+        jameboy.memory.write(Address(0xFF50), 1);
+        jameboy.load_cartridge(program);
+    }
+
+    #[test]
+    fn test_linked_pair_exchanges_bytes() {
+        // LD A, $12; LDH [SB], A; LD A, $81; LDH [SC], A;
+        // wait: LDH A, [SC]; BIT 7, A; JR NZ, wait; JR @
+        let master = [
+            0x3E, 0x12, 0xE0, 0x01, 0x3E, 0x81, 0xE0, 0x02, 0xF0, 0x02, 0xCB, 0x7F, 0x20, 0xFA,
+            0x18, 0xFE,
+        ];
+        // As above but with SB = $34 and SC = $80 to wait on the master's clock
+        let slave = [
+            0x3E, 0x34, 0xE0, 0x01, 0x3E, 0x80, 0xE0, 0x02, 0xF0, 0x02, 0xCB, 0x7F, 0x20, 0xFA,
+            0x18, 0xFE,
+        ];
+
+        let mut pair = LinkedPair::init();
+        load_program(&mut pair.left, &master);
+        load_program(&mut pair.right, &slave);
+
+        for _ in 0..2000 {
+            pair.step();
+        }
+
+        assert_eq!(0x34, pair.left.memory.read(Address(ADDRESS_SB)));
+        assert_eq!(0x12, pair.right.memory.read(Address(ADDRESS_SB)));
+        assert_eq!(0x0E, pair.left.cpu.pc);
+        assert_eq!(0x0E, pair.right.cpu.pc);
+    }
+
+    #[test]
+    fn test_tcp_link_reports_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let mut link = TcpLink::from_stream(stream).unwrap();
+        drop(peer);
+
+        while link.connected() {
+            assert_eq!(None, link.receive());
+        }
+        assert!(matches!(link.take_event(), Some(LinkEvent::Disconnected(_))));
+        assert!(link.take_event().is_none());
+    }
+}
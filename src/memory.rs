@@ -4,9 +4,15 @@ use derive_more::LowerHex;
 use rand::rngs::SmallRng;
 use rand::{RngCore, SeedableRng};
 
+use crate::state::{StateError, StateReader, StateWriter};
 use crate::util::u8_to_u16;
 
+const ADDRESS_INTERRUPT_FLAG_REGISTER: u16 = 0xFF0F;
 const ADDRESS_DMA: u16 = 0xFF46;
+const VRAM: std::ops::Range<usize> = 0x8000..0xA000;
+const WORK_RAM: std::ops::Range<usize> = 0xC000..0xE000;
+const OAM: std::ops::Range<usize> = 0xFE00..0xFEA0;
+const HIGH_RAM: std::ops::Range<usize> = 0xFF80..0xFFFF;
 
 #[derive(LowerHex, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Address(pub u16);
@@ -24,17 +30,24 @@ pub struct Memory {
 
 impl Memory {
     pub fn init() -> Self {
-        let mut memory = [0; 0x10000];
-        // let mut rng = SmallRng::from_entropy();
-        // rng.fill_bytes(&mut memory);
-
         Self {
             bootstrap_rom: [0; 0x100],
             dma_state: DmaState::Inactive,
-            ram: memory,
+            ram: [0; 0x10000],
         }
     }
 
+    /// Work RAM and high RAM power on holding garbage. The garbage comes from `seed` so runs can be
+    /// reproduced.
+    pub fn init_with_seed(seed: u64) -> Self {
+        let mut memory = Self::init();
+        let mut rng = SmallRng::seed_from_u64(seed);
+        rng.fill_bytes(&mut memory.ram[WORK_RAM]);
+        rng.fill_bytes(&mut memory.ram[HIGH_RAM]);
+
+        memory
+    }
+
     pub fn load_bootstrap_rom(&mut self, rom: &[u8]) {
         for i in 0..rom.len() {
             self.bootstrap_rom[i] = rom[i];
@@ -46,13 +59,11 @@ impl Memory {
         //     return last_transferred_byte;
         // }
 
-        if address.0 == 0xFF44 {
-            // Uncomment the following line if testing with gameboy-doctor
-            // return 0x90;
-        }
-
         if self.ram[0xFF50] == 0 && address.0 < 0x100 {
             self.bootstrap_rom[usize::from(address.0)]
+        } else if address.0 == ADDRESS_INTERRUPT_FLAG_REGISTER {
+            // Only the low 5 bits of IF exist, the rest read as 1
+            self.ram[usize::from(address.0)] | 0b1110_0000
         } else {
             self.ram[usize::from(address.0)]
         }
@@ -64,6 +75,16 @@ impl Memory {
         &self.ram[start..start + count as usize]
     }
 
+    /// VRAM, borrowed in place for reading a whole scanline's tiles without going through `read`.
+    pub fn vram(&self) -> &[u8] {
+        &self.ram[VRAM]
+    }
+
+    /// OAM, borrowed in place like `vram`.
+    pub fn oam(&self) -> &[u8] {
+        &self.ram[OAM]
+    }
+
     pub fn step(&mut self) {
         if let DmaState::Active { src_addr, cycles, .. } = self.dma_state {
             let dst_address = u8_to_u16(0xFE, cycles);
@@ -77,6 +98,35 @@ impl Memory {
         }
     }
 
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.bytes(&self.bootstrap_rom);
+        writer.bytes(&self.ram);
+        match self.dma_state {
+            DmaState::Inactive => writer.bool(false),
+            DmaState::Active { src_addr, cycles, last_transferred_byte } => {
+                writer.bool(true);
+                writer.u16(src_addr);
+                writer.u8(cycles);
+                writer.u8(last_transferred_byte);
+            }
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        reader.bytes_into(&mut self.bootstrap_rom, "bootstrap ROM")?;
+        reader.bytes_into(&mut self.ram, "RAM")?;
+        self.dma_state = match reader.bool()? {
+            false => DmaState::Inactive,
+            true => DmaState::Active {
+                src_addr: reader.u16()?,
+                cycles: reader.u8()?,
+                last_transferred_byte: reader.u8()?,
+            },
+        };
+
+        Ok(())
+    }
+
     pub fn write(&mut self, address: Address, value: u8) {
         if let DmaState::Active { .. } = self.dma_state {
             if address.0 < 0xFF80 || address.0 > 0xFFFE {
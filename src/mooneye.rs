@@ -0,0 +1,66 @@
+use crate::memory::Address;
+use crate::Jameboy;
+
+/// Mooneye test ROMs execute `LD B, B` as a software breakpoint once they have a result.
+const OPCODE_LD_B_B: u8 = 0x40;
+
+/// B, C, D, E, H and L of a passing test. Failing tests load 0x42 into all of them instead.
+const PASS_SIGNATURE: [u8; 6] = [3, 5, 8, 13, 21, 34];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// The registers B, C, D, E, H and L at the breakpoint
+    Fail([u8; 6]),
+    Timeout,
+}
+
+/// Runs a Mooneye test ROM until it hits its `LD B, B` breakpoint, or gives up after
+/// `timeout_cycles` M-cycles.
+pub fn run(jameboy: &mut Jameboy, timeout_cycles: usize) -> Outcome {
+    let mut cycles = 0;
+    while cycles < timeout_cycles {
+        let cpu = &jameboy.cpu;
+        if !cpu.prefixed && jameboy.memory.read(Address(cpu.pc)) == OPCODE_LD_B_B {
+            let registers = [cpu.b, cpu.c, cpu.d, cpu.e, cpu.h, cpu.l];
+            return match registers == PASS_SIGNATURE {
+                true => Outcome::Pass,
+                false => Outcome::Fail(registers),
+            };
+        }
+
+        cycles += jameboy.step();
+    }
+
+    Outcome::Timeout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boot::Model;
+
+    fn run_program(program: &[u8]) -> Outcome {
+        let mut rom = vec![0; 0x8000];
+        rom[0x100..0x100 + program.len()].copy_from_slice(program);
+
+        let mut jameboy = Jameboy::init();
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+        run(&mut jameboy, 1000)
+    }
+
+    #[test]
+    fn test_run() {
+        // LD BC, $0305; LD DE, $080D; LD HL, $1522; LD B, B
+        let pass = [0x01, 0x05, 0x03, 0x11, 0x0D, 0x08, 0x21, 0x22, 0x15, 0x40];
+        assert_eq!(Outcome::Pass, run_program(&pass));
+
+        // LD BC, $4242; LD D, B; LD E, B; LD H, B; LD L, B; LD B, B
+        let fail = [0x01, 0x42, 0x42, 0x50, 0x58, 0x60, 0x68, 0x40];
+        assert_eq!(Outcome::Fail([0x42; 6]), run_program(&fail));
+
+        // JR @
+        assert_eq!(Outcome::Timeout, run_program(&[0x18, 0xFE]));
+    }
+}
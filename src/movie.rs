@@ -0,0 +1,234 @@
+use crate::boot::Model;
+use crate::joypad::{self, Buttons};
+use crate::ppu::RendererKind;
+use crate::state::{StateError, StateReader, StateWriter};
+use crate::Jameboy;
+
+const MAGIC: [u8; 4] = *b"JMBM";
+const VERSION: u16 = 1;
+
+/// How the machine was started, which a replay has to match exactly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MovieSettings {
+    pub rom_hash: u64,
+    /// Hash of the bootstrap ROM, or None if the movie starts from the post-boot state of `model`.
+    pub bootstrap_rom_hash: Option<u64>,
+    pub model: Model,
+    /// Seed used to fill RAM at power on, see `Jameboy::init_with_seed`.
+    pub seed: Option<u64>,
+    pub renderer: RendererKind,
+}
+
+/// Joypad input for every frame since power on.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Movie {
+    pub settings: MovieSettings,
+    frames: Vec<Buttons>,
+}
+
+impl Movie {
+    pub fn init(settings: MovieSettings) -> Self {
+        Self {
+            settings,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The input held during `frame`, or None past the end of the movie.
+    pub fn input(&self, frame: usize) -> Option<Buttons> {
+        self.frames.get(frame).copied()
+    }
+
+    /// Records the input for `frame`. Anything recorded after it is discarded, so recording again
+    /// after rewinding carries on from there.
+    pub fn record(&mut self, frame: usize, buttons: Buttons) {
+        let held = self.frames.last().copied().unwrap_or_default();
+        self.frames.resize(frame, held);
+        self.frames.push(buttons);
+    }
+
+    pub fn save(&self) -> Vec<u8> {
+        let settings = &self.settings;
+        let mut writer = StateWriter::with_header(MAGIC, VERSION);
+        writer.chunk(*b"INFO", |w| {
+            w.u64(settings.rom_hash);
+            w.bool(settings.bootstrap_rom_hash.is_some());
+            w.u64(settings.bootstrap_rom_hash.unwrap_or(0));
+            w.u8(match settings.model {
+                Model::Dmg => 0,
+                Model::Mgb => 1,
+                Model::Cgb => 2,
+            });
+            w.bool(settings.seed.is_some());
+            w.u64(settings.seed.unwrap_or(0));
+        });
+        writer.chunk(*b"REND", |w| settings.renderer.save_state(w));
+        writer.chunk(*b"INPT", |w| {
+            w.u32(self.frames.len() as u32);
+            for buttons in &self.frames {
+                joypad::save_state(w, *buttons);
+            }
+        });
+
+        writer.finish()
+    }
+
+    pub fn load(data: &[u8]) -> Result<Self, StateError> {
+        let chunks = StateReader::chunks_with_header(data, MAGIC, VERSION)?;
+
+        let mut info = chunks.get(*b"INFO")?;
+        let rom_hash = info.u64()?;
+        let bootstrap_rom_hash = optional_u64(&mut info)?;
+        let model = match info.u8()? {
+            0 => Model::Dmg,
+            1 => Model::Mgb,
+            2 => Model::Cgb,
+            _ => return Err(StateError::Invalid("model")),
+        };
+        let seed = optional_u64(&mut info)?;
+        // Movies recorded before the renderer could be picked were all drawn by the FIFO renderer
+        let renderer = match chunks.get(*b"REND") {
+            Ok(mut reader) => RendererKind::load_state(&mut reader)?,
+            Err(_) => RendererKind::Fifo,
+        };
+
+        let mut input = chunks.get(*b"INPT")?;
+        let frames = (0..input.u32()?)
+            .map(|_| joypad::load_state(&mut input))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            settings: MovieSettings {
+                rom_hash,
+                bootstrap_rom_hash,
+                model,
+                seed,
+                renderer,
+            },
+            frames,
+        })
+    }
+}
+
+fn optional_u64(reader: &mut StateReader) -> Result<Option<u64>, StateError> {
+    let present = reader.bool()?;
+    let value = reader.u64()?;
+
+    Ok(present.then_some(value))
+}
+
+/// FNV-1a, to tell ROMs apart rather than to guard against tampering.
+pub fn rom_hash(rom: &[u8]) -> u64 {
+    rom.iter().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+/// Builds a machine in the power-on state described by `settings`.
+/// Returns None if either ROM doesn't match the one the settings were recorded with.
+pub fn power_on(settings: &MovieSettings, rom: &[u8], bootstrap_rom: Option<&[u8]>) -> Option<Jameboy> {
+    if rom_hash(rom) != settings.rom_hash || bootstrap_rom.map(rom_hash) != settings.bootstrap_rom_hash {
+        return None;
+    }
+
+    let mut jameboy = match settings.seed {
+        Some(seed) => Jameboy::init_with_seed(seed),
+        None => Jameboy::init(),
+    };
+    match bootstrap_rom {
+        Some(bootstrap_rom) => {
+            jameboy.load_bootstrap_rom(bootstrap_rom);
+            jameboy.load_cartridge(rom);
+        }
+        None => {
+            jameboy.load_cartridge(rom);
+            jameboy.skip_bootstrap_rom(settings.model);
+        }
+    }
+    jameboy.set_renderer(settings.renderer);
+
+    Some(jameboy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Address;
+
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0; 0x8000];
+        // loop: LDH A, [$00]; LD [HL+], A; JR loop
+        rom[0x100..0x105].copy_from_slice(&[0xF0, 0x00, 0x22, 0x18, 0xFB]);
+        rom
+    }
+
+    fn settings(rom: &[u8]) -> MovieSettings {
+        MovieSettings {
+            rom_hash: rom_hash(rom),
+            bootstrap_rom_hash: None,
+            model: Model::Dmg,
+            seed: Some(1234),
+            renderer: RendererKind::Scanline,
+        }
+    }
+
+    fn play(movie: &Movie, rom: &[u8]) -> Vec<u8> {
+        let mut jameboy = power_on(&movie.settings, rom, None).unwrap();
+        for frame in 0..movie.len() {
+            jameboy.set_input(movie.input(frame).unwrap());
+            jameboy.run_frame();
+        }
+        jameboy.save_state()
+    }
+
+    #[test]
+    fn test_movie_round_trip() {
+        let rom = rom();
+        let mut movie = Movie::init(settings(&rom));
+        movie.record(0, Buttons::default());
+        movie.record(3, Buttons { up: true, ..Default::default() });
+        movie.record(4, Buttons { up: true, a: true, ..Default::default() });
+        assert_eq!(5, movie.len());
+        assert_eq!(Some(Buttons::default()), movie.input(2));
+
+        let loaded = Movie::load(&movie.save()).unwrap();
+        assert_eq!(movie, loaded);
+        assert_eq!(play(&movie, &rom), play(&loaded, &rom));
+
+        // Recording over an earlier frame drops what came after it
+        movie.record(2, Buttons::default());
+        assert_eq!(3, movie.len());
+    }
+
+    #[test]
+    fn test_power_on() {
+        let rom = rom();
+        let settings = settings(&rom);
+
+        let first = power_on(&settings, &rom, None).unwrap();
+        let second = power_on(&settings, &rom, None).unwrap();
+        assert_eq!(first.save_state(), second.save_state());
+        assert_ne!(0, (0xC000..0xE000).map(|a| first.memory.read(Address(a)) as u32).sum::<u32>());
+
+        assert!(power_on(&settings, &rom[1..], None).is_none());
+        assert!(power_on(&settings, &rom, Some(&[0; 0x100])).is_none());
+    }
+
+    #[test]
+    fn test_power_on_uses_renderer() {
+        let rom = rom();
+        let movie = Movie::init(settings(&rom));
+        let loaded = Movie::load(&movie.save()).unwrap();
+
+        let jameboy = power_on(&loaded.settings, &rom, None).unwrap();
+        assert_eq!(RendererKind::Scanline, jameboy.ppu.renderer());
+    }
+}
@@ -1,3255 +1,4748 @@
 use crate::cpu::{Cpu, Register, RegisterWide, WriteFlags};
-use crate::memory::{Address, Memory};
-use crate::util::{self, half_carried_add16, half_carried_add8, half_carried_sub8, u16_to_u8, u8_to_u16};
+use crate::bus::Bus;
+use crate::memory::Address;
+use crate::util::{self, half_carried_add16, half_carried_add8, half_carried_sub8, u8_to_u16};
 
-type OpcodeHandler = fn(cpu: &mut Cpu, memory: &mut Memory);
+type OpcodeHandler = fn(cpu: &mut Cpu, bus: &mut dyn Bus);
+
+/// The immediate operand that follows an opcode, if any.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operand {
+    None,
+    /// 8-bit immediate
+    N8,
+    /// 16-bit immediate, little-endian
+    N16,
+    /// Signed 8-bit offset
+    E8,
+    /// Low byte of an address in 0xFF00-0xFFFF
+    A8,
+    /// 16-bit address, little-endian
+    A16,
+}
+
+/// M-cycles an instruction takes, including fetching its opcode and, for prefixed opcodes, the
+/// 0xCB prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cycles {
+    Fixed(u8),
+    /// Conditional jumps, calls and returns take longer when the branch is taken.
+    Branch { taken: u8, not_taken: u8 },
+}
 
 #[derive(Debug)]
 pub struct Opcode {
-    pub mnemonic: String,
+    pub mnemonic: &'static str,
     pub size_bytes: u8,
+    pub operand: Operand,
+    pub cycles: Cycles,
+    /// Set for 0xCB, whose following opcode is looked up with `decode_prefixed`.
+    pub prefix: bool,
     pub handler: Option<OpcodeHandler>,
 }
 
 impl Opcode {
-    pub fn execute(&self, cpu: &mut Cpu, memory: &mut Memory) {
+    pub fn execute(&self, cpu: &mut Cpu, bus: &mut dyn Bus) {
         match self.handler {
-            Some(handler) => handler(cpu, memory),
+            Some(handler) => handler(cpu, bus),
             None => println!("Unimplemented opcode: {:?}", self)
         };
     }
 }
 
-pub fn decode(byte: u8) -> Option<Opcode> {
-    match byte {
-        0x00 => Some(Opcode {
-            mnemonic: "NOP".to_string(),
-            size_bytes: 1,
-            handler: Some(|_, _| {}),
-        }),
-        0x01 => Some(Opcode {
-            mnemonic: "LD BC, n16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r16_n16(cpu, memory, RegisterWide::BC)
-            }),
-        }),
-        0x02 => Some(Opcode {
-            mnemonic: "LD [BC], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::BC,
-                    Register::A,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x03 => Some(Opcode {
-            mnemonic: "INC BC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r16(cpu, RegisterWide::BC)),
-        }),
-        0x04 => Some(Opcode {
-            mnemonic: "INC B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::B)),
-        }),
-        0x05 => Some(Opcode {
-            mnemonic: "DEC B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::B)),
-        }),
-        0x06 => Some(Opcode {
-            mnemonic: "LD B, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::B)),
-        }),
-        0x07 => Some(Opcode {
-            mnemonic: "RLCA".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let value = cpu.read_register(Register::A);
-                let result = value.rotate_left(1);
-
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(value & 0b1000_0000 != 0),
-                });
-                cpu.write_register(Register::A, result);
-            }),
-        }),
-        0x08 => Some(Opcode {
-            mnemonic: "LD [a16], SP".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let lsb = memory.read(Address(pc - 2));
-                let msb = memory.read(Address(pc - 1));
-                let address = util::u8_to_u16(msb, lsb);
-                let sp = cpu.read_register_wide(RegisterWide::SP);
-                let (sp_msb, sp_lsb) = util::u16_to_u8(sp);
-                memory.write(Address(address), sp_lsb);
-                memory.write(Address(address + 1), sp_msb);
-            }),
-        }),
-        0x09 => Some(Opcode {
-            mnemonic: "ADD HL, BC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| add_hl_r16(cpu, RegisterWide::BC)),
-        }),
-        0x0A => Some(Opcode {
-            mnemonic: "LD A, [BC]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::A,
-                    RegisterWide::BC,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x0B => Some(Opcode {
-            mnemonic: "DEC BC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r16(cpu, RegisterWide::BC)),
-        }),
-        0x0C => Some(Opcode {
-            mnemonic: "INC C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::C)),
-        }),
-        0x0D => Some(Opcode {
-            mnemonic: "DEC C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::C)),
-        }),
-        0x0E => Some(Opcode {
-            mnemonic: "LD C, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::C)),
-        }),
-        0x0F => Some(Opcode {
-            mnemonic: "RRCA ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let value = cpu.read_register(Register::A);
-                let result = value.rotate_right(1);
-
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(value & 0b0000_0001 != 0),
-                });
-                cpu.write_register(Register::A, result);
-            }),
-        }),
-        0x10 => Some(Opcode {
-            mnemonic: "STOP n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                // This should stop the CPU and LCD but I don't think this is important right now,
-                // if ever.
-            }),
-        }),
-        0x11 => Some(Opcode {
-            mnemonic: "LD DE, n16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r16_n16(cpu, memory, RegisterWide::DE)
-            }),
-        }),
-        0x12 => Some(Opcode {
-            mnemonic: "LD [DE], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::DE,
-                    Register::A,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x13 => Some(Opcode {
-            mnemonic: "INC DE".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r16(cpu, RegisterWide::DE)),
-        }),
-        0x14 => Some(Opcode {
-            mnemonic: "INC D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::D)),
-        }),
-        0x15 => Some(Opcode {
-            mnemonic: "DEC D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::D)),
-        }),
-        0x16 => Some(Opcode {
-            mnemonic: "LD D, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::D)),
-        }),
-        0x17 => Some(Opcode {
-            mnemonic: "RLA".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let value = cpu.read_register(Register::A);
-                let mut result = value << 1;
-                if cpu.read_flags().carry {
-                    result |= 0b0000_0001;
-                }
+pub fn decode(byte: u8) -> Option<&'static Opcode> {
+    OPCODES[usize::from(byte)].as_ref()
+}
 
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(value & 0b1000_0000 != 0),
-                });
-                cpu.write_register(Register::A, result);
-            })
-        }),
-        0x18 => Some(Opcode {
-            mnemonic: "JR e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| jump_relative(cpu, memory)),
-        }),
-        0x19 => Some(Opcode {
-            mnemonic: "ADD HL, DE".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| add_hl_r16(cpu, RegisterWide::DE)),
-        }),
-        0x1A => Some(Opcode {
-            mnemonic: "LD A, [DE]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::A,
-                    RegisterWide::DE,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x1B => Some(Opcode {
-            mnemonic: "DEC DE".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r16(cpu, RegisterWide::DE)),
-        }),
-        0x1C => Some(Opcode {
-            mnemonic: "INC E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::E)),
-        }),
-        0x1D => Some(Opcode {
-            mnemonic: "DEC E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::E)),
-        }),
-        0x1E => Some(Opcode {
-            mnemonic: "LD E, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::E)),
-        }),
-        0x1F => Some(Opcode {
-            mnemonic: "RRA ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let value = cpu.read_register(Register::A);
-                let mut result = value >> 1;
-                if cpu.read_flags().carry {
-                    result |= 0b1000_0000;
-                }
+/// Decodes the opcode following a 0xCB prefix.
+pub fn decode_prefixed(byte: u8) -> Option<&'static Opcode> {
+    PREFIXED_OPCODES[usize::from(byte)].as_ref()
+}
 
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(value & 0b0000_0001 != 0),
-                });
-                cpu.write_register(Register::A, result);
-            })
-        }),
-        0x20 => Some(Opcode {
-            mnemonic: "JR NZ, e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let flags = cpu.read_flags();
-                if !flags.zero {
-                    jump_relative(cpu, memory);
-                }
-            }),
-        }),
-        0x21 => Some(Opcode {
-            mnemonic: "LD HL, n16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r16_n16(cpu, memory, RegisterWide::HL)
-            }),
-        }),
-        0x22 => Some(Opcode {
-            mnemonic: "LD [HL+], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::A,
-                    IndirectAddressingMode::Increment,
-                )
-            }),
-        }),
-        0x23 => Some(Opcode {
-            mnemonic: "INC HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r16(cpu, RegisterWide::HL)),
-        }),
-        0x24 => Some(Opcode {
-            mnemonic: "INC H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::H)),
-        }),
-        0x25 => Some(Opcode {
-            mnemonic: "DEC H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::H)),
-        }),
-        0x26 => Some(Opcode {
-            mnemonic: "LD H, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::H)),
-        }),
-        0x27 => Some(Opcode {
-            mnemonic: "DAA ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let half_carry = cpu.read_flags().half_carry;
-                let carry = cpu.read_flags().carry;
-                let subtract = cpu.read_flags().subtract;
-
-                let mut offset = 0_u8;
-                if (!subtract && a & 0xF > 0x09) || half_carry {
-                    offset |= 0x06;
-                }
+static OPCODES: [Option<Opcode>; 256] = {
+    let mut opcodes = [const { None }; 256];
+    opcodes[0x00] = Some(Opcode {
+        mnemonic: "NOP",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|_, _| {}),
+    });
+    opcodes[0x01] = Some(Opcode {
+        mnemonic: "LD BC, n16",
+        size_bytes: 3,
+        operand: Operand::N16,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r16_n16(cpu, bus, RegisterWide::BC)
+        }),
+    });
+    opcodes[0x02] = Some(Opcode {
+        mnemonic: "LD [BC], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::BC,
+                Register::A,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x03] = Some(Opcode {
+        mnemonic: "INC BC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| inc_r16(cpu, bus, RegisterWide::BC)),
+    });
+    opcodes[0x04] = Some(Opcode {
+        mnemonic: "INC B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::B)),
+    });
+    opcodes[0x05] = Some(Opcode {
+        mnemonic: "DEC B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::B)),
+    });
+    opcodes[0x06] = Some(Opcode {
+        mnemonic: "LD B, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::B)),
+    });
+    opcodes[0x07] = Some(Opcode {
+        mnemonic: "RLCA",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            let value = cpu.read_register(Register::A);
+            let result = value.rotate_left(1);
 
-                let mut carried = false;
-                if (!subtract && a > 0x99) || carry {
-                    offset |= 0x60;
-                    carried = true;
-                }
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(value & 0b1000_0000 != 0),
+            });
+            cpu.write_register(Register::A, result);
+        }),
+    });
+    opcodes[0x08] = Some(Opcode {
+        mnemonic: "LD [a16], SP",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Fixed(5),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let lsb = bus.read(Address(pc - 2));
+            let msb = bus.read(Address(pc - 1));
+            let address = util::u8_to_u16(msb, lsb);
+            let sp = cpu.read_register_wide(RegisterWide::SP);
+            let (sp_msb, sp_lsb) = util::u16_to_u8(sp);
+            bus.write(Address(address), sp_lsb);
+            bus.write(Address(address + 1), sp_msb);
+        }),
+    });
+    opcodes[0x09] = Some(Opcode {
+        mnemonic: "ADD HL, BC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| add_hl_r16(cpu, bus, RegisterWide::BC)),
+    });
+    opcodes[0x0A] = Some(Opcode {
+        mnemonic: "LD A, [BC]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::A,
+                RegisterWide::BC,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x0B] = Some(Opcode {
+        mnemonic: "DEC BC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| dec_r16(cpu, bus, RegisterWide::BC)),
+    });
+    opcodes[0x0C] = Some(Opcode {
+        mnemonic: "INC C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::C)),
+    });
+    opcodes[0x0D] = Some(Opcode {
+        mnemonic: "DEC C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::C)),
+    });
+    opcodes[0x0E] = Some(Opcode {
+        mnemonic: "LD C, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::C)),
+    });
+    opcodes[0x0F] = Some(Opcode {
+        mnemonic: "RRCA",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            let value = cpu.read_register(Register::A);
+            let result = value.rotate_right(1);
 
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(value & 0b0000_0001 != 0),
+            });
+            cpu.write_register(Register::A, result);
+        }),
+    });
+    opcodes[0x10] = Some(Opcode {
+        mnemonic: "STOP n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            // This should stop the CPU and LCD but I don't think this is important right now,
+            // if ever.
+        }),
+    });
+    opcodes[0x11] = Some(Opcode {
+        mnemonic: "LD DE, n16",
+        size_bytes: 3,
+        operand: Operand::N16,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r16_n16(cpu, bus, RegisterWide::DE)
+        }),
+    });
+    opcodes[0x12] = Some(Opcode {
+        mnemonic: "LD [DE], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::DE,
+                Register::A,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x13] = Some(Opcode {
+        mnemonic: "INC DE",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| inc_r16(cpu, bus, RegisterWide::DE)),
+    });
+    opcodes[0x14] = Some(Opcode {
+        mnemonic: "INC D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::D)),
+    });
+    opcodes[0x15] = Some(Opcode {
+        mnemonic: "DEC D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::D)),
+    });
+    opcodes[0x16] = Some(Opcode {
+        mnemonic: "LD D, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::D)),
+    });
+    opcodes[0x17] = Some(Opcode {
+        mnemonic: "RLA",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let value = cpu.read_register(Register::A);
+            let mut result = value << 1;
+            if cpu.read_flags().carry {
+                result |= 0b0000_0001;
+            }
 
-                let result = if subtract {
-                    a.wrapping_sub(offset)
-                } else {
-                    a.wrapping_add(offset)
-                };
-
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: None,
-                    half_carry: Some(false),
-                    carry: Some(carried),
-                });
-            }),
-        }),
-        0x28 => Some(Opcode {
-            mnemonic: "JR Z, e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let flags = cpu.read_flags();
-                if !flags.zero {
-                    return;
-                }
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(value & 0b1000_0000 != 0),
+            });
+            cpu.write_register(Register::A, result);
+        })
+    });
+    opcodes[0x18] = Some(Opcode {
+        mnemonic: "JR e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| jump_relative(cpu, bus, true)),
+    });
+    opcodes[0x19] = Some(Opcode {
+        mnemonic: "ADD HL, DE",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| add_hl_r16(cpu, bus, RegisterWide::DE)),
+    });
+    opcodes[0x1A] = Some(Opcode {
+        mnemonic: "LD A, [DE]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::A,
+                RegisterWide::DE,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x1B] = Some(Opcode {
+        mnemonic: "DEC DE",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| dec_r16(cpu, bus, RegisterWide::DE)),
+    });
+    opcodes[0x1C] = Some(Opcode {
+        mnemonic: "INC E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::E)),
+    });
+    opcodes[0x1D] = Some(Opcode {
+        mnemonic: "DEC E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::E)),
+    });
+    opcodes[0x1E] = Some(Opcode {
+        mnemonic: "LD E, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::E)),
+    });
+    opcodes[0x1F] = Some(Opcode {
+        mnemonic: "RRA",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let value = cpu.read_register(Register::A);
+            let mut result = value >> 1;
+            if cpu.read_flags().carry {
+                result |= 0b1000_0000;
+            }
 
-                jump_relative(cpu, memory);
-            }),
-        }),
-        0x29 => Some(Opcode {
-            mnemonic: "ADD HL, HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_hl_r16(cpu, RegisterWide::HL)),
-        }),
-        0x2A => Some(Opcode {
-            mnemonic: "LD A, [HL+]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::A,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Increment,
-                )
-            }),
-        }),
-        0x2B => Some(Opcode {
-            mnemonic: "DEC HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r16(cpu, RegisterWide::HL)),
-        }),
-        0x2C => Some(Opcode {
-            mnemonic: "INC L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::L)),
-        }),
-        0x2D => Some(Opcode {
-            mnemonic: "DEC L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::L)),
-        }),
-        0x2E => Some(Opcode {
-            mnemonic: "LD L, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::L)),
-        }),
-        0x2F => Some(Opcode {
-            mnemonic: "CPL ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let a = cpu.read_register(Register::A);
-                cpu.write_register(Register::A, !a);
-                cpu.write_flags(WriteFlags {
-                    subtract: Some(true),
-                    half_carry: Some(true),
-                    ..Default::default()
-                });
-            }),
-        }),
-        0x30 => Some(Opcode {
-            mnemonic: "JR NC, e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().carry {
-                    return;
-                }
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(value & 0b0000_0001 != 0),
+            });
+            cpu.write_register(Register::A, result);
+        })
+    });
+    opcodes[0x20] = Some(Opcode {
+        mnemonic: "JR NZ, e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Branch { taken: 3, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().zero;
+            jump_relative(cpu, bus, taken);
+        }),
+    });
+    opcodes[0x21] = Some(Opcode {
+        mnemonic: "LD HL, n16",
+        size_bytes: 3,
+        operand: Operand::N16,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r16_n16(cpu, bus, RegisterWide::HL)
+        }),
+    });
+    opcodes[0x22] = Some(Opcode {
+        mnemonic: "LD [HL+], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::A,
+                IndirectAddressingMode::Increment,
+            )
+        }),
+    });
+    opcodes[0x23] = Some(Opcode {
+        mnemonic: "INC HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| inc_r16(cpu, bus, RegisterWide::HL)),
+    });
+    opcodes[0x24] = Some(Opcode {
+        mnemonic: "INC H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::H)),
+    });
+    opcodes[0x25] = Some(Opcode {
+        mnemonic: "DEC H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::H)),
+    });
+    opcodes[0x26] = Some(Opcode {
+        mnemonic: "LD H, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::H)),
+    });
+    opcodes[0x27] = Some(Opcode {
+        mnemonic: "DAA",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let half_carry = cpu.read_flags().half_carry;
+            let carry = cpu.read_flags().carry;
+            let subtract = cpu.read_flags().subtract;
 
-                jump_relative(cpu, memory);
-            }),
-        }),
-        0x31 => Some(Opcode {
-            mnemonic: "LD SP, n16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r16_n16(cpu, memory, RegisterWide::SP)
-            }),
-        }),
-        0x32 => Some(Opcode {
-            mnemonic: "LD [HL-], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::A,
-                    IndirectAddressingMode::Decrement,
-                )
-            }),
-        }),
-        0x33 => Some(Opcode {
-            mnemonic: "INC SP".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r16(cpu, RegisterWide::SP)),
-        }),
-        0x34 => Some(Opcode {
-            mnemonic: "INC [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let value = memory.read(Address(hl));
-                let result = value.wrapping_add(1);
-                memory.write(Address(hl), result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried_add8(value, 1)),
-                    carry: None,
-                });
-            }),
-        }),
-        0x35 => Some(Opcode {
-            mnemonic: "DEC [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let value = memory.read(Address(hl));
-                let (result, _) = value.overflowing_sub(1);
-                memory.write(Address(hl), result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(true),
-                    half_carry: Some(half_carried_sub8(value, 1)),
-                    carry: None,
-                });
-            }),
-        }),
-        0x36 => Some(Opcode {
-            mnemonic: "LD [HL], n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let dst_value = cpu.read_register_wide(RegisterWide::HL);
-                let src_value = memory.read(Address(pc - 1));
-                memory.write(Address(dst_value), src_value);
-            }),
-        }),
-        0x37 => Some(Opcode {
-            mnemonic: "SCF ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                cpu.write_flags(WriteFlags {
-                    zero: None,
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(true),
-                });
-            }),
-        }),
-        0x38 => Some(Opcode {
-            mnemonic: "JR C, e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().carry {
-                    return;
-                }
+            let mut offset = 0_u8;
+            if (!subtract && a & 0xF > 0x09) || half_carry {
+                offset |= 0x06;
+            }
 
-                jump_relative(cpu, memory);
-            }),
-        }),
-        0x39 => Some(Opcode {
-            mnemonic: "ADD HL, SP".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| add_hl_r16(cpu, RegisterWide::SP)),
-        }),
-        0x3A => Some(Opcode {
-            mnemonic: "LD A, [HL-]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::A,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Decrement,
-                )
-            }),
-        }),
-        0x3B => Some(Opcode {
-            mnemonic: "DEC SP".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r16(cpu, RegisterWide::SP)),
-        }),
-        0x3C => Some(Opcode {
-            mnemonic: "INC A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::A)),
-        }),
-        0x3D => Some(Opcode {
-            mnemonic: "DEC A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::A)),
-        }),
-        0x3E => Some(Opcode {
-            mnemonic: "LD A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| ld_r8_n8(cpu, memory, Register::A)),
-        }),
-        0x3F => Some(Opcode {
-            mnemonic: "CCF ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let flags = cpu.read_flags();
-                cpu.write_flags(WriteFlags {
-                    zero: None,
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(!flags.carry),
-                });
-            }),
-        }),
-        0x40 => Some(Opcode {
-            mnemonic: "LD B, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::B)),
-        }),
-        0x41 => Some(Opcode {
-            mnemonic: "LD B, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::C)),
-        }),
-        0x42 => Some(Opcode {
-            mnemonic: "LD B, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::D)),
-        }),
-        0x43 => Some(Opcode {
-            mnemonic: "LD B, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::E)),
-        }),
-        0x44 => Some(Opcode {
-            mnemonic: "LD B, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::H)),
-        }),
-        0x45 => Some(Opcode {
-            mnemonic: "LD B, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::L)),
-        }),
-        0x46 => Some(Opcode {
-            mnemonic: "LD B, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::B,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x47 => Some(Opcode {
-            mnemonic: "LD B, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::B, Register::A)),
-        }),
-        0x48 => Some(Opcode {
-            mnemonic: "LD C, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::B)),
-        }),
-        0x49 => Some(Opcode {
-            mnemonic: "LD C, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::C)),
-        }),
-        0x4A => Some(Opcode {
-            mnemonic: "LD C, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::D)),
-        }),
-        0x4B => Some(Opcode {
-            mnemonic: "LD C, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::E)),
-        }),
-        0x4C => Some(Opcode {
-            mnemonic: "LD C, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::H)),
-        }),
-        0x4D => Some(Opcode {
-            mnemonic: "LD C, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::L)),
-        }),
-        0x4E => Some(Opcode {
-            mnemonic: "LD C, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::C,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x4F => Some(Opcode {
-            mnemonic: "LD C, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::C, Register::A)),
-        }),
-        0x50 => Some(Opcode {
-            mnemonic: "LD D, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::B)),
-        }),
-        0x51 => Some(Opcode {
-            mnemonic: "LD D, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::C)),
-        }),
-        0x52 => Some(Opcode {
-            mnemonic: "LD D, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::D)),
-        }),
-        0x53 => Some(Opcode {
-            mnemonic: "LD D, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::E)),
-        }),
-        0x54 => Some(Opcode {
-            mnemonic: "LD D, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::H)),
-        }),
-        0x55 => Some(Opcode {
-            mnemonic: "LD D, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::L)),
-        }),
-        0x56 => Some(Opcode {
-            mnemonic: "LD D, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::D,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x57 => Some(Opcode {
-            mnemonic: "LD D, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::D, Register::A)),
-        }),
-        0x58 => Some(Opcode {
-            mnemonic: "LD E, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::B)),
-        }),
-        0x59 => Some(Opcode {
-            mnemonic: "LD E, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::C)),
-        }),
-        0x5A => Some(Opcode {
-            mnemonic: "LD E, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::D)),
-        }),
-        0x5B => Some(Opcode {
-            mnemonic: "LD E, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::E)),
-        }),
-        0x5C => Some(Opcode {
-            mnemonic: "LD E, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::H)),
-        }),
-        0x5D => Some(Opcode {
-            mnemonic: "LD E, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::L)),
-        }),
-        0x5E => Some(Opcode {
-            mnemonic: "LD E, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::E,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x5F => Some(Opcode {
-            mnemonic: "LD E, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::E, Register::A)),
-        }),
-        0x60 => Some(Opcode {
-            mnemonic: "LD H, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::B)),
-        }),
-        0x61 => Some(Opcode {
-            mnemonic: "LD H, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::C)),
-        }),
-        0x62 => Some(Opcode {
-            mnemonic: "LD H, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::D)),
-        }),
-        0x63 => Some(Opcode {
-            mnemonic: "LD H, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::E)),
-        }),
-        0x64 => Some(Opcode {
-            mnemonic: "LD H, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::H)),
-        }),
-        0x65 => Some(Opcode {
-            mnemonic: "LD H, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::L)),
-        }),
-        0x66 => Some(Opcode {
-            mnemonic: "LD H, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::H,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x67 => Some(Opcode {
-            mnemonic: "LD H, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::H, Register::A)),
-        }),
-        0x68 => Some(Opcode {
-            mnemonic: "LD L, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::B)),
-        }),
-        0x69 => Some(Opcode {
-            mnemonic: "LD L, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::C)),
-        }),
-        0x6A => Some(Opcode {
-            mnemonic: "LD L, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::D)),
-        }),
-        0x6B => Some(Opcode {
-            mnemonic: "LD L, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::E)),
-        }),
-        0x6C => Some(Opcode {
-            mnemonic: "LD L, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::H)),
-        }),
-        0x6D => Some(Opcode {
-            mnemonic: "LD L, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::L)),
-        }),
-        0x6E => Some(Opcode {
-            mnemonic: "LD L, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::L,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x6F => Some(Opcode {
-            mnemonic: "LD L, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::L, Register::A)),
-        }),
-        0x70 => Some(Opcode {
-            mnemonic: "LD [HL], B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::B,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x71 => Some(Opcode {
-            mnemonic: "LD [HL], C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::C,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x72 => Some(Opcode {
-            mnemonic: "LD [HL], D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::D,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x73 => Some(Opcode {
-            mnemonic: "LD [HL], E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::E,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x74 => Some(Opcode {
-            mnemonic: "LD [HL], H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::H,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x75 => Some(Opcode {
-            mnemonic: "LD [HL], L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::L,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x76 => Some(Opcode {
-            mnemonic: "HALT ".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x77 => Some(Opcode {
-            mnemonic: "LD [HL], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_indirect_r16_r8(
-                    cpu,
-                    memory,
-                    RegisterWide::HL,
-                    Register::A,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x78 => Some(Opcode {
-            mnemonic: "LD A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::B)),
-        }),
-        0x79 => Some(Opcode {
-            mnemonic: "LD A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::C)),
-        }),
-        0x7A => Some(Opcode {
-            mnemonic: "LD A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::D)),
-        }),
-        0x7B => Some(Opcode {
-            mnemonic: "LD A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::E)),
-        }),
-        0x7C => Some(Opcode {
-            mnemonic: "LD A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::H)),
-        }),
-        0x7D => Some(Opcode {
-            mnemonic: "LD A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::L)),
-        }),
-        0x7E => Some(Opcode {
-            mnemonic: "LD A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                ld_r8_indirect_r16(
-                    cpu,
-                    memory,
-                    Register::A,
-                    RegisterWide::HL,
-                    IndirectAddressingMode::Retain,
-                )
-            }),
-        }),
-        0x7F => Some(Opcode {
-            mnemonic: "LD A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _: &mut Memory| ld_r8_r8(cpu, Register::A, Register::A)),
-        }),
-        0x80 => Some(Opcode {
-            mnemonic: "ADD A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::B)),
-        }),
-        0x81 => Some(Opcode {
-            mnemonic: "ADD A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::C)),
-        }),
-        0x82 => Some(Opcode {
-            mnemonic: "ADD A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::D)),
-        }),
-        0x83 => Some(Opcode {
-            mnemonic: "ADD A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::E)),
-        }),
-        0x84 => Some(Opcode {
-            mnemonic: "ADD A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::H)),
-        }),
-        0x85 => Some(Opcode {
-            mnemonic: "ADD A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::L)),
-        }),
-        0x86 => Some(Opcode {
-            mnemonic: "ADD A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let b = memory.read(Address(hl));
-                let (result, overflowed) = a.overflowing_add(b);
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried_add8(a, b)),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0x87 => Some(Opcode {
-            mnemonic: "ADD A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::A)),
-        }),
-        0x88 => Some(Opcode {
-            mnemonic: "ADC A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::B)),
-        }),
-        0x89 => Some(Opcode {
-            mnemonic: "ADC A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::C)),
-        }),
-        0x8A => Some(Opcode {
-            mnemonic: "ADC A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::D)),
-        }),
-        0x8B => Some(Opcode {
-            mnemonic: "ADC A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::E)),
-        }),
-        0x8C => Some(Opcode {
-            mnemonic: "ADC A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::H)),
-        }),
-        0x8D => Some(Opcode {
-            mnemonic: "ADC A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::L)),
-        }),
-        0x8E => Some(Opcode {
-            mnemonic: "ADC A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let carry_bit = cpu.read_flags().carry as u8;
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let hl_value = memory.read(Address(hl));
-                let b = hl_value.wrapping_add(carry_bit);
-                let (mut result, mut overflowed) = a.overflowing_add(b);
-
-                if cpu.read_flags().carry {
-                    let (carry_result, carry_overflowed) = result.overflowing_add(1);
-                    result = carry_result;
-                    overflowed |= carry_overflowed;
-                }
+            let mut carried = false;
+            if (!subtract && a > 0x99) || carry {
+                offset |= 0x60;
+                carried = true;
+            }
 
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried_add8(a, b)),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0x8F => Some(Opcode {
-            mnemonic: "ADC A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::A)),
-        }),
-        0x90 => Some(Opcode {
-            mnemonic: "SUB A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::B)),
-        }),
-        0x91 => Some(Opcode {
-            mnemonic: "SUB A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::C)),
-        }),
-        0x92 => Some(Opcode {
-            mnemonic: "SUB A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::D)),
-        }),
-        0x93 => Some(Opcode {
-            mnemonic: "SUB A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::E)),
-        }),
-        0x94 => Some(Opcode {
-            mnemonic: "SUB A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::H)),
-        }),
-        0x95 => Some(Opcode {
-            mnemonic: "SUB A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::L)),
-        }),
-        0x96 => Some(Opcode {
-            mnemonic: "SUB A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x97 => Some(Opcode {
-            mnemonic: "SUB A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::A)),
-        }),
-        0x98 => Some(Opcode {
-            mnemonic: "SBC A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::B)),
-        }),
-        0x99 => Some(Opcode {
-            mnemonic: "SBC A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::C)),
-        }),
-        0x9A => Some(Opcode {
-            mnemonic: "SBC A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::D)),
-        }),
-        0x9B => Some(Opcode {
-            mnemonic: "SBC A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::E)),
-        }),
-        0x9C => Some(Opcode {
-            mnemonic: "SBC A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::H)),
-        }),
-        0x9D => Some(Opcode {
-            mnemonic: "SBC A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::L)),
-        }),
-        0x9E => Some(Opcode {
-            mnemonic: "SBC A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x9F => Some(Opcode {
-            mnemonic: "SBC A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::A)),
-        }),
-        0xA0 => Some(Opcode {
-            mnemonic: "AND A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::B)),
-        }),
-        0xA1 => Some(Opcode {
-            mnemonic: "AND A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::C)),
-        }),
-        0xA2 => Some(Opcode {
-            mnemonic: "AND A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::D)),
-        }),
-        0xA3 => Some(Opcode {
-            mnemonic: "AND A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::E)),
-        }),
-        0xA4 => Some(Opcode {
-            mnemonic: "AND A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::H)),
-        }),
-        0xA5 => Some(Opcode {
-            mnemonic: "AND A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::L)),
-        }),
-        0xA6 => Some(Opcode {
-            mnemonic: "AND A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0xA7 => Some(Opcode {
-            mnemonic: "AND A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::A)),
-        }),
-        0xA8 => Some(Opcode {
-            mnemonic: "XOR A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::B)),
-        }),
-        0xA9 => Some(Opcode {
-            mnemonic: "XOR A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::C)),
-        }),
-        0xAA => Some(Opcode {
-            mnemonic: "XOR A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::D)),
-        }),
-        0xAB => Some(Opcode {
-            mnemonic: "XOR A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::E)),
-        }),
-        0xAC => Some(Opcode {
-            mnemonic: "XOR A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::H)),
-        }),
-        0xAD => Some(Opcode {
-            mnemonic: "XOR A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::L)),
-        }),
-        0xAE => Some(Opcode {
-            mnemonic: "XOR A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let value = memory.read(Address(hl));
-                let result = a ^ value;
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(false),
-                });
-            })
-        }),
-        0xAF => Some(Opcode {
-            mnemonic: "XOR A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::A)),
-        }),
-        0xB0 => Some(Opcode {
-            mnemonic: "OR A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::B)),
-        }),
-        0xB1 => Some(Opcode {
-            mnemonic: "OR A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::C)),
-        }),
-        0xB2 => Some(Opcode {
-            mnemonic: "OR A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::D)),
-        }),
-        0xB3 => Some(Opcode {
-            mnemonic: "OR A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::E)),
-        }),
-        0xB4 => Some(Opcode {
-            mnemonic: "OR A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::H)),
-        }),
-        0xB5 => Some(Opcode {
-            mnemonic: "OR A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::L)),
-        }),
-        0xB6 => Some(Opcode {
-            mnemonic: "OR A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let b = memory.read(Address(hl));
-                let result = a | b;
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(false),
-                });
-            }),
-        }),
-        0xB7 => Some(Opcode {
-            mnemonic: "OR A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::A)),
-        }),
-        0xB8 => Some(Opcode {
-            mnemonic: "CP A, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::B)),
-        }),
-        0xB9 => Some(Opcode {
-            mnemonic: "CP A, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::C)),
-        }),
-        0xBA => Some(Opcode {
-            mnemonic: "CP A, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::D)),
-        }),
-        0xBB => Some(Opcode {
-            mnemonic: "CP A, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::E)),
-        }),
-        0xBC => Some(Opcode {
-            mnemonic: "CP A, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::H)),
-        }),
-        0xBD => Some(Opcode {
-            mnemonic: "CP A, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::L)),
-        }),
-        0xBE => Some(Opcode {
-            mnemonic: "CP A, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                let b = memory.read(Address(hl));
-                let (result, overflowed) = a.overflowing_sub(b);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(true),
-                    half_carry: Some(half_carried_sub8(a, b)),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0xBF => Some(Opcode {
-            mnemonic: "CP A, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::A)),
-        }),
-        0xC0 => Some(Opcode {
-            mnemonic: "RET NZ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().zero {
-                    return;
-                }
 
-                pop(cpu, memory, RegisterWide::PC);
-            }),
+            let result = if subtract {
+                a.wrapping_sub(offset)
+            } else {
+                a.wrapping_add(offset)
+            };
+
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: None,
+                half_carry: Some(false),
+                carry: Some(carried),
+            });
         }),
-        0xC1 => Some(Opcode {
-            mnemonic: "POP BC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| pop(cpu, memory, RegisterWide::BC)),
+    });
+    opcodes[0x28] = Some(Opcode {
+        mnemonic: "JR Z, e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Branch { taken: 3, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().zero;
+            jump_relative(cpu, bus, taken);
         }),
-        0xC2 => Some(Opcode {
-            mnemonic: "JP NZ, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().zero {
-                    return;
-                }
-
-                jump(cpu, memory);
-            }),
-        }),
-        0xC3 => Some(Opcode {
-            mnemonic: "JP a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let msb = memory.read(Address(pc - 1));
-                let lsb = memory.read(Address(pc - 2));
-                let nn = u8_to_u16(msb, lsb);
-                cpu.write_register_wide(RegisterWide::PC, nn);
-            }),
-        }),
-        0xC4 => Some(Opcode {
-            mnemonic: "CALL NZ, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().zero {
-                    return;
-                }
+    });
+    opcodes[0x29] = Some(Opcode {
+        mnemonic: "ADD HL, HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| add_hl_r16(cpu, bus, RegisterWide::HL)),
+    });
+    opcodes[0x2A] = Some(Opcode {
+        mnemonic: "LD A, [HL+]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::A,
+                RegisterWide::HL,
+                IndirectAddressingMode::Increment,
+            )
+        }),
+    });
+    opcodes[0x2B] = Some(Opcode {
+        mnemonic: "DEC HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| dec_r16(cpu, bus, RegisterWide::HL)),
+    });
+    opcodes[0x2C] = Some(Opcode {
+        mnemonic: "INC L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::L)),
+    });
+    opcodes[0x2D] = Some(Opcode {
+        mnemonic: "DEC L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::L)),
+    });
+    opcodes[0x2E] = Some(Opcode {
+        mnemonic: "LD L, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::L)),
+    });
+    opcodes[0x2F] = Some(Opcode {
+        mnemonic: "CPL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            let a = cpu.read_register(Register::A);
+            cpu.write_register(Register::A, !a);
+            cpu.write_flags(WriteFlags {
+                subtract: Some(true),
+                half_carry: Some(true),
+                ..Default::default()
+            });
+        }),
+    });
+    opcodes[0x30] = Some(Opcode {
+        mnemonic: "JR NC, e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Branch { taken: 3, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().carry;
+            jump_relative(cpu, bus, taken);
+        }),
+    });
+    opcodes[0x31] = Some(Opcode {
+        mnemonic: "LD SP, n16",
+        size_bytes: 3,
+        operand: Operand::N16,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r16_n16(cpu, bus, RegisterWide::SP)
+        }),
+    });
+    opcodes[0x32] = Some(Opcode {
+        mnemonic: "LD [HL-], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::A,
+                IndirectAddressingMode::Decrement,
+            )
+        }),
+    });
+    opcodes[0x33] = Some(Opcode {
+        mnemonic: "INC SP",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| inc_r16(cpu, bus, RegisterWide::SP)),
+    });
+    opcodes[0x34] = Some(Opcode {
+        mnemonic: "INC [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let value = bus.read(Address(hl));
+            let result = value.wrapping_add(1);
+            bus.write(Address(hl), result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(half_carried_add8(value, 1)),
+                carry: None,
+            });
+        }),
+    });
+    opcodes[0x35] = Some(Opcode {
+        mnemonic: "DEC [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let value = bus.read(Address(hl));
+            let (result, _) = value.overflowing_sub(1);
+            bus.write(Address(hl), result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(true),
+                half_carry: Some(half_carried_sub8(value, 1)),
+                carry: None,
+            });
+        }),
+    });
+    opcodes[0x36] = Some(Opcode {
+        mnemonic: "LD [HL], n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let dst_value = cpu.read_register_wide(RegisterWide::HL);
+            let src_value = bus.read(Address(pc - 1));
+            bus.write(Address(dst_value), src_value);
+        }),
+    });
+    opcodes[0x37] = Some(Opcode {
+        mnemonic: "SCF",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            cpu.write_flags(WriteFlags {
+                zero: None,
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(true),
+            });
+        }),
+    });
+    opcodes[0x38] = Some(Opcode {
+        mnemonic: "JR C, e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Branch { taken: 3, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().carry;
+            jump_relative(cpu, bus, taken);
+        }),
+    });
+    opcodes[0x39] = Some(Opcode {
+        mnemonic: "ADD HL, SP",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| add_hl_r16(cpu, bus, RegisterWide::SP)),
+    });
+    opcodes[0x3A] = Some(Opcode {
+        mnemonic: "LD A, [HL-]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::A,
+                RegisterWide::HL,
+                IndirectAddressingMode::Decrement,
+            )
+        }),
+    });
+    opcodes[0x3B] = Some(Opcode {
+        mnemonic: "DEC SP",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| dec_r16(cpu, bus, RegisterWide::SP)),
+    });
+    opcodes[0x3C] = Some(Opcode {
+        mnemonic: "INC A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| inc_r8(cpu, Register::A)),
+    });
+    opcodes[0x3D] = Some(Opcode {
+        mnemonic: "DEC A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| dec_r8(cpu, Register::A)),
+    });
+    opcodes[0x3E] = Some(Opcode {
+        mnemonic: "LD A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ld_r8_n8(cpu, bus, Register::A)),
+    });
+    opcodes[0x3F] = Some(Opcode {
+        mnemonic: "CCF",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            let flags = cpu.read_flags();
+            cpu.write_flags(WriteFlags {
+                zero: None,
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(!flags.carry),
+            });
+        }),
+    });
+    opcodes[0x40] = Some(Opcode {
+        mnemonic: "LD B, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::B)),
+    });
+    opcodes[0x41] = Some(Opcode {
+        mnemonic: "LD B, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::C)),
+    });
+    opcodes[0x42] = Some(Opcode {
+        mnemonic: "LD B, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::D)),
+    });
+    opcodes[0x43] = Some(Opcode {
+        mnemonic: "LD B, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::E)),
+    });
+    opcodes[0x44] = Some(Opcode {
+        mnemonic: "LD B, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::H)),
+    });
+    opcodes[0x45] = Some(Opcode {
+        mnemonic: "LD B, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::L)),
+    });
+    opcodes[0x46] = Some(Opcode {
+        mnemonic: "LD B, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::B,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x47] = Some(Opcode {
+        mnemonic: "LD B, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::B, Register::A)),
+    });
+    opcodes[0x48] = Some(Opcode {
+        mnemonic: "LD C, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::B)),
+    });
+    opcodes[0x49] = Some(Opcode {
+        mnemonic: "LD C, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::C)),
+    });
+    opcodes[0x4A] = Some(Opcode {
+        mnemonic: "LD C, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::D)),
+    });
+    opcodes[0x4B] = Some(Opcode {
+        mnemonic: "LD C, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::E)),
+    });
+    opcodes[0x4C] = Some(Opcode {
+        mnemonic: "LD C, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::H)),
+    });
+    opcodes[0x4D] = Some(Opcode {
+        mnemonic: "LD C, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::L)),
+    });
+    opcodes[0x4E] = Some(Opcode {
+        mnemonic: "LD C, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::C,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x4F] = Some(Opcode {
+        mnemonic: "LD C, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::C, Register::A)),
+    });
+    opcodes[0x50] = Some(Opcode {
+        mnemonic: "LD D, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::B)),
+    });
+    opcodes[0x51] = Some(Opcode {
+        mnemonic: "LD D, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::C)),
+    });
+    opcodes[0x52] = Some(Opcode {
+        mnemonic: "LD D, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::D)),
+    });
+    opcodes[0x53] = Some(Opcode {
+        mnemonic: "LD D, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::E)),
+    });
+    opcodes[0x54] = Some(Opcode {
+        mnemonic: "LD D, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::H)),
+    });
+    opcodes[0x55] = Some(Opcode {
+        mnemonic: "LD D, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::L)),
+    });
+    opcodes[0x56] = Some(Opcode {
+        mnemonic: "LD D, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::D,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x57] = Some(Opcode {
+        mnemonic: "LD D, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::D, Register::A)),
+    });
+    opcodes[0x58] = Some(Opcode {
+        mnemonic: "LD E, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::B)),
+    });
+    opcodes[0x59] = Some(Opcode {
+        mnemonic: "LD E, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::C)),
+    });
+    opcodes[0x5A] = Some(Opcode {
+        mnemonic: "LD E, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::D)),
+    });
+    opcodes[0x5B] = Some(Opcode {
+        mnemonic: "LD E, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::E)),
+    });
+    opcodes[0x5C] = Some(Opcode {
+        mnemonic: "LD E, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::H)),
+    });
+    opcodes[0x5D] = Some(Opcode {
+        mnemonic: "LD E, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::L)),
+    });
+    opcodes[0x5E] = Some(Opcode {
+        mnemonic: "LD E, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::E,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x5F] = Some(Opcode {
+        mnemonic: "LD E, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::E, Register::A)),
+    });
+    opcodes[0x60] = Some(Opcode {
+        mnemonic: "LD H, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::B)),
+    });
+    opcodes[0x61] = Some(Opcode {
+        mnemonic: "LD H, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::C)),
+    });
+    opcodes[0x62] = Some(Opcode {
+        mnemonic: "LD H, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::D)),
+    });
+    opcodes[0x63] = Some(Opcode {
+        mnemonic: "LD H, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::E)),
+    });
+    opcodes[0x64] = Some(Opcode {
+        mnemonic: "LD H, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::H)),
+    });
+    opcodes[0x65] = Some(Opcode {
+        mnemonic: "LD H, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::L)),
+    });
+    opcodes[0x66] = Some(Opcode {
+        mnemonic: "LD H, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::H,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x67] = Some(Opcode {
+        mnemonic: "LD H, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::H, Register::A)),
+    });
+    opcodes[0x68] = Some(Opcode {
+        mnemonic: "LD L, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::B)),
+    });
+    opcodes[0x69] = Some(Opcode {
+        mnemonic: "LD L, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::C)),
+    });
+    opcodes[0x6A] = Some(Opcode {
+        mnemonic: "LD L, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::D)),
+    });
+    opcodes[0x6B] = Some(Opcode {
+        mnemonic: "LD L, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::E)),
+    });
+    opcodes[0x6C] = Some(Opcode {
+        mnemonic: "LD L, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::H)),
+    });
+    opcodes[0x6D] = Some(Opcode {
+        mnemonic: "LD L, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::L)),
+    });
+    opcodes[0x6E] = Some(Opcode {
+        mnemonic: "LD L, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::L,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x6F] = Some(Opcode {
+        mnemonic: "LD L, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::L, Register::A)),
+    });
+    opcodes[0x70] = Some(Opcode {
+        mnemonic: "LD [HL], B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::B,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x71] = Some(Opcode {
+        mnemonic: "LD [HL], C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::C,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x72] = Some(Opcode {
+        mnemonic: "LD [HL], D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::D,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x73] = Some(Opcode {
+        mnemonic: "LD [HL], E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::E,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x74] = Some(Opcode {
+        mnemonic: "LD [HL], H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::H,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x75] = Some(Opcode {
+        mnemonic: "LD [HL], L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::L,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x76] = Some(Opcode {
+        mnemonic: "HALT",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x77] = Some(Opcode {
+        mnemonic: "LD [HL], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_indirect_r16_r8(
+                cpu,
+                bus,
+                RegisterWide::HL,
+                Register::A,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x78] = Some(Opcode {
+        mnemonic: "LD A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::B)),
+    });
+    opcodes[0x79] = Some(Opcode {
+        mnemonic: "LD A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::C)),
+    });
+    opcodes[0x7A] = Some(Opcode {
+        mnemonic: "LD A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::D)),
+    });
+    opcodes[0x7B] = Some(Opcode {
+        mnemonic: "LD A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::E)),
+    });
+    opcodes[0x7C] = Some(Opcode {
+        mnemonic: "LD A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::H)),
+    });
+    opcodes[0x7D] = Some(Opcode {
+        mnemonic: "LD A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::L)),
+    });
+    opcodes[0x7E] = Some(Opcode {
+        mnemonic: "LD A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            ld_r8_indirect_r16(
+                cpu,
+                bus,
+                Register::A,
+                RegisterWide::HL,
+                IndirectAddressingMode::Retain,
+            )
+        }),
+    });
+    opcodes[0x7F] = Some(Opcode {
+        mnemonic: "LD A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _: &mut dyn Bus| ld_r8_r8(cpu, Register::A, Register::A)),
+    });
+    opcodes[0x80] = Some(Opcode {
+        mnemonic: "ADD A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::B)),
+    });
+    opcodes[0x81] = Some(Opcode {
+        mnemonic: "ADD A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::C)),
+    });
+    opcodes[0x82] = Some(Opcode {
+        mnemonic: "ADD A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::D)),
+    });
+    opcodes[0x83] = Some(Opcode {
+        mnemonic: "ADD A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::E)),
+    });
+    opcodes[0x84] = Some(Opcode {
+        mnemonic: "ADD A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::H)),
+    });
+    opcodes[0x85] = Some(Opcode {
+        mnemonic: "ADD A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::L)),
+    });
+    opcodes[0x86] = Some(Opcode {
+        mnemonic: "ADD A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let b = bus.read(Address(hl));
+            let (result, overflowed) = a.overflowing_add(b);
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(half_carried_add8(a, b)),
+                carry: Some(overflowed),
+            });
+        }),
+    });
+    opcodes[0x87] = Some(Opcode {
+        mnemonic: "ADD A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| add_r8(cpu, Register::A)),
+    });
+    opcodes[0x88] = Some(Opcode {
+        mnemonic: "ADC A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::B)),
+    });
+    opcodes[0x89] = Some(Opcode {
+        mnemonic: "ADC A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::C)),
+    });
+    opcodes[0x8A] = Some(Opcode {
+        mnemonic: "ADC A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::D)),
+    });
+    opcodes[0x8B] = Some(Opcode {
+        mnemonic: "ADC A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::E)),
+    });
+    opcodes[0x8C] = Some(Opcode {
+        mnemonic: "ADC A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::H)),
+    });
+    opcodes[0x8D] = Some(Opcode {
+        mnemonic: "ADC A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::L)),
+    });
+    opcodes[0x8E] = Some(Opcode {
+        mnemonic: "ADC A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let carry_bit = cpu.read_flags().carry as u8;
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let hl_value = bus.read(Address(hl));
+            let b = hl_value.wrapping_add(carry_bit);
+            let (mut result, mut overflowed) = a.overflowing_add(b);
 
-                call_a16(cpu, memory);
-            }),
-        }),
-        0xC5 => Some(Opcode {
-            mnemonic: "PUSH BC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| push(cpu, memory, RegisterWide::BC)),
-        }),
-        0xC6 => Some(Opcode {
-            mnemonic: "ADD A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let (result, overflowed) = a.overflowing_add(imm);
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried_add8(a, imm)),
-                    carry: Some(overflowed),
-                });
-            })
-        }),
-        0xC7 => Some(Opcode {
-            mnemonic: "RST $00".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x00)),
-        }),
-        0xC8 => Some(Opcode {
-            mnemonic: "RET Z".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().zero {
-                    return;
-                }
+            if cpu.read_flags().carry {
+                let (carry_result, carry_overflowed) = result.overflowing_add(1);
+                result = carry_result;
+                overflowed |= carry_overflowed;
+            }
 
-                pop(cpu, memory, RegisterWide::PC);
-            }),
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(half_carried_add8(a, b)),
+                carry: Some(overflowed),
+            });
+        }),
+    });
+    opcodes[0x8F] = Some(Opcode {
+        mnemonic: "ADC A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| adc_r8(cpu, Register::A)),
+    });
+    opcodes[0x90] = Some(Opcode {
+        mnemonic: "SUB A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::B)),
+    });
+    opcodes[0x91] = Some(Opcode {
+        mnemonic: "SUB A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::C)),
+    });
+    opcodes[0x92] = Some(Opcode {
+        mnemonic: "SUB A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::D)),
+    });
+    opcodes[0x93] = Some(Opcode {
+        mnemonic: "SUB A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::E)),
+    });
+    opcodes[0x94] = Some(Opcode {
+        mnemonic: "SUB A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::H)),
+    });
+    opcodes[0x95] = Some(Opcode {
+        mnemonic: "SUB A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::L)),
+    });
+    opcodes[0x96] = Some(Opcode {
+        mnemonic: "SUB A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x97] = Some(Opcode {
+        mnemonic: "SUB A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sub_r8(cpu, Register::A)),
+    });
+    opcodes[0x98] = Some(Opcode {
+        mnemonic: "SBC A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::B)),
+    });
+    opcodes[0x99] = Some(Opcode {
+        mnemonic: "SBC A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::C)),
+    });
+    opcodes[0x9A] = Some(Opcode {
+        mnemonic: "SBC A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::D)),
+    });
+    opcodes[0x9B] = Some(Opcode {
+        mnemonic: "SBC A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::E)),
+    });
+    opcodes[0x9C] = Some(Opcode {
+        mnemonic: "SBC A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::H)),
+    });
+    opcodes[0x9D] = Some(Opcode {
+        mnemonic: "SBC A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::L)),
+    });
+    opcodes[0x9E] = Some(Opcode {
+        mnemonic: "SBC A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x9F] = Some(Opcode {
+        mnemonic: "SBC A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| sbc_r8(cpu, Register::A)),
+    });
+    opcodes[0xA0] = Some(Opcode {
+        mnemonic: "AND A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::B)),
+    });
+    opcodes[0xA1] = Some(Opcode {
+        mnemonic: "AND A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::C)),
+    });
+    opcodes[0xA2] = Some(Opcode {
+        mnemonic: "AND A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::D)),
+    });
+    opcodes[0xA3] = Some(Opcode {
+        mnemonic: "AND A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::E)),
+    });
+    opcodes[0xA4] = Some(Opcode {
+        mnemonic: "AND A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::H)),
+    });
+    opcodes[0xA5] = Some(Opcode {
+        mnemonic: "AND A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::L)),
+    });
+    opcodes[0xA6] = Some(Opcode {
+        mnemonic: "AND A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0xA7] = Some(Opcode {
+        mnemonic: "AND A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| and_r8(cpu, Register::A)),
+    });
+    opcodes[0xA8] = Some(Opcode {
+        mnemonic: "XOR A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::B)),
+    });
+    opcodes[0xA9] = Some(Opcode {
+        mnemonic: "XOR A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::C)),
+    });
+    opcodes[0xAA] = Some(Opcode {
+        mnemonic: "XOR A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::D)),
+    });
+    opcodes[0xAB] = Some(Opcode {
+        mnemonic: "XOR A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::E)),
+    });
+    opcodes[0xAC] = Some(Opcode {
+        mnemonic: "XOR A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::H)),
+    });
+    opcodes[0xAD] = Some(Opcode {
+        mnemonic: "XOR A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::L)),
+    });
+    opcodes[0xAE] = Some(Opcode {
+        mnemonic: "XOR A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let value = bus.read(Address(hl));
+            let result = a ^ value;
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(false),
+            });
+        })
+    });
+    opcodes[0xAF] = Some(Opcode {
+        mnemonic: "XOR A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| xor_r8(cpu, Register::A)),
+    });
+    opcodes[0xB0] = Some(Opcode {
+        mnemonic: "OR A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::B)),
+    });
+    opcodes[0xB1] = Some(Opcode {
+        mnemonic: "OR A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::C)),
+    });
+    opcodes[0xB2] = Some(Opcode {
+        mnemonic: "OR A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::D)),
+    });
+    opcodes[0xB3] = Some(Opcode {
+        mnemonic: "OR A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::E)),
+    });
+    opcodes[0xB4] = Some(Opcode {
+        mnemonic: "OR A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::H)),
+    });
+    opcodes[0xB5] = Some(Opcode {
+        mnemonic: "OR A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::L)),
+    });
+    opcodes[0xB6] = Some(Opcode {
+        mnemonic: "OR A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let b = bus.read(Address(hl));
+            let result = a | b;
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(false),
+            });
+        }),
+    });
+    opcodes[0xB7] = Some(Opcode {
+        mnemonic: "OR A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| or_r8(cpu, Register::A)),
+    });
+    opcodes[0xB8] = Some(Opcode {
+        mnemonic: "CP A, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::B)),
+    });
+    opcodes[0xB9] = Some(Opcode {
+        mnemonic: "CP A, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::C)),
+    });
+    opcodes[0xBA] = Some(Opcode {
+        mnemonic: "CP A, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::D)),
+    });
+    opcodes[0xBB] = Some(Opcode {
+        mnemonic: "CP A, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::E)),
+    });
+    opcodes[0xBC] = Some(Opcode {
+        mnemonic: "CP A, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::H)),
+    });
+    opcodes[0xBD] = Some(Opcode {
+        mnemonic: "CP A, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::L)),
+    });
+    opcodes[0xBE] = Some(Opcode {
+        mnemonic: "CP A, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            let b = bus.read(Address(hl));
+            let (result, overflowed) = a.overflowing_sub(b);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(true),
+                half_carry: Some(half_carried_sub8(a, b)),
+                carry: Some(overflowed),
+            });
         }),
-        0xC9 => Some(Opcode {
-            mnemonic: "RET".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| pop(cpu, memory, RegisterWide::PC)),
+    });
+    opcodes[0xBF] = Some(Opcode {
+        mnemonic: "CP A, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| cp_r8(cpu, Register::A)),
+    });
+    opcodes[0xC0] = Some(Opcode {
+        mnemonic: "RET NZ",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Branch { taken: 5, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().zero;
+            ret_conditional(cpu, bus, taken);
         }),
-        0xCA => Some(Opcode {
-            mnemonic: "JP Z, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().zero {
-                    return;
-                }
+    });
+    opcodes[0xC1] = Some(Opcode {
+        mnemonic: "POP BC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| pop(cpu, bus, RegisterWide::BC)),
+    });
+    opcodes[0xC2] = Some(Opcode {
+        mnemonic: "JP NZ, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 4, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().zero;
+            jump(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xC3] = Some(Opcode {
+        mnemonic: "JP a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| jump(cpu, bus, true)),
+    });
+    opcodes[0xC4] = Some(Opcode {
+        mnemonic: "CALL NZ, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 6, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().zero;
+            call_a16(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xC5] = Some(Opcode {
+        mnemonic: "PUSH BC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| push(cpu, bus, RegisterWide::BC)),
+    });
+    opcodes[0xC6] = Some(Opcode {
+        mnemonic: "ADD A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let (result, overflowed) = a.overflowing_add(imm);
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(half_carried_add8(a, imm)),
+                carry: Some(overflowed),
+            });
+        })
+    });
+    opcodes[0xC7] = Some(Opcode {
+        mnemonic: "RST $00",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x00)),
+    });
+    opcodes[0xC8] = Some(Opcode {
+        mnemonic: "RET Z",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Branch { taken: 5, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().zero;
+            ret_conditional(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xC9] = Some(Opcode {
+        mnemonic: "RET",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| ret(cpu, bus)),
+    });
+    opcodes[0xCA] = Some(Opcode {
+        mnemonic: "JP Z, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 4, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().zero;
+            jump(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xCB] = Some(Opcode {
+        mnemonic: "PREFIX",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: true,
+        handler: Some(|_, _| {}),
+    });
+    opcodes[0xCC] = Some(Opcode {
+        mnemonic: "CALL Z, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 6, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().zero;
+            call_a16(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xCD] = Some(Opcode {
+        mnemonic: "CALL a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Fixed(6),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| call_a16(cpu, bus, true)),
+    });
+    opcodes[0xCE] = Some(Opcode {
+        mnemonic: "ADC A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let (mut result, mut overflowed) = a.overflowing_add(imm);
+            let mut half_carried = util::half_carried_add8(a, imm);
 
-                jump(cpu, memory);
-            }),
-        }),
-        0xCB => Some(Opcode {
-            mnemonic: "PREFIX".to_string(),
-            size_bytes: 1,
-            handler: Some(|_, _| {}),
-        }),
-        0xCC => Some(Opcode {
-            mnemonic: "CALL Z, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let zero = cpu.read_flags().zero;
-                if !zero {
-                    return;
-                }
+            if cpu.read_flags().carry {
+                half_carried |= util::half_carried_add8(result, 1);
+                let (carry_result, carry_overflowed) = result.overflowing_add(1);
+                overflowed |= carry_overflowed;
+                result = carry_result;
+            }
 
-                call_a16(cpu, memory);
-            }),
-        }),
-        0xCD => Some(Opcode {
-            mnemonic: "CALL a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| call_a16(cpu, memory)),
-        }),
-        0xCE => Some(Opcode {
-            mnemonic: "ADC A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let (mut result, mut overflowed) = a.overflowing_add(imm);
-                let mut half_carried = util::half_carried_add8(a, imm);
-
-                if cpu.read_flags().carry {
-                    half_carried |= util::half_carried_add8(result, 1);
-                    let (carry_result, carry_overflowed) = result.overflowing_add(1);
-                    overflowed |= carry_overflowed;
-                    result = carry_result;
-                }
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(half_carried),
+                carry: Some(overflowed),
+            });
+        }),
+    });
+    opcodes[0xCF] = Some(Opcode {
+        mnemonic: "RST $08",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x08)),
+    });
+    opcodes[0xD0] = Some(Opcode {
+        mnemonic: "RET NC",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Branch { taken: 5, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().carry;
+            ret_conditional(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xD1] = Some(Opcode {
+        mnemonic: "POP DE",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| pop(cpu, bus, RegisterWide::DE)),
+    });
+    opcodes[0xD2] = Some(Opcode {
+        mnemonic: "JP NC, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 4, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().carry;
+            jump(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xD4] = Some(Opcode {
+        mnemonic: "CALL NC, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 6, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = !cpu.read_flags().carry;
+            call_a16(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xD5] = Some(Opcode {
+        mnemonic: "PUSH DE",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| push(cpu, bus, RegisterWide::DE)),
+    });
+    opcodes[0xD6] = Some(Opcode {
+        mnemonic: "SUB A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let (result, overflowed) = a.overflowing_sub(imm);
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(true),
+                half_carry: Some(half_carried_sub8(a, imm)),
+                carry: Some(overflowed),
+            });
+        })
+    });
+    opcodes[0xD7] = Some(Opcode {
+        mnemonic: "RST $10",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x10)),
+    });
+    opcodes[0xD8] = Some(Opcode {
+        mnemonic: "RET C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Branch { taken: 5, not_taken: 2 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().carry;
+            ret_conditional(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xD9] = Some(Opcode {
+        mnemonic: "RETI",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            cpu.request_ime_enable();
+            ret(cpu, bus);
+        }),
+    });
+    opcodes[0xDA] = Some(Opcode {
+        mnemonic: "JP C, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 4, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().carry;
+            jump(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xDC] = Some(Opcode {
+        mnemonic: "CALL C, a16",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Branch { taken: 6, not_taken: 3 },
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let taken = cpu.read_flags().carry;
+            call_a16(cpu, bus, taken);
+        }),
+    });
+    opcodes[0xDE] = Some(Opcode {
+        mnemonic: "SBC A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
 
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0xCF => Some(Opcode {
-            mnemonic: "RST $08".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x08)),
-        }),
-        0xD0 => Some(Opcode {
-            mnemonic: "RET NC".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().carry {
-                    return;
-                }
+            let (mut result, mut overflowed) = a.overflowing_sub(imm);
+            let mut half_carried = util::half_carried_sub8(a, imm);
+            
+            if cpu.read_flags().carry {
+                half_carried |= util::half_carried_sub8(result, 1);
+                let (carry_result, carry_overflowed) = result.overflowing_sub(1);
+                overflowed |= carry_overflowed;
+                result = carry_result;
+            }
 
-                pop(cpu, memory, RegisterWide::PC);
-            }),
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(true),
+                half_carry: Some(half_carried),
+                carry: Some(overflowed),
+            });
+        }),
+    });
+    opcodes[0xDF] = Some(Opcode {
+        mnemonic: "RST $18",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x18)),
+    });
+    opcodes[0xE0] = Some(Opcode {
+        mnemonic: "LDH [a8], A",
+        size_bytes: 2,
+        operand: Operand::A8,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let address = u8_to_u16(0xFF, imm);
+            let a = cpu.read_register(Register::A);
+            bus.write(Address(address), a);
         }),
-        0xD1 => Some(Opcode {
-            mnemonic: "POP DE".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| pop(cpu, memory, RegisterWide::DE)),
+    });
+    opcodes[0xE1] = Some(Opcode {
+        mnemonic: "POP HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| pop(cpu, bus, RegisterWide::HL)),
+    });
+    opcodes[0xE2] = Some(Opcode {
+        mnemonic: "LD [C], A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let c = cpu.read_register(Register::C);
+            let address = Address(util::u8_to_u16(0xFF, c));
+            bus.write(address, a);
         }),
-        0xD2 => Some(Opcode {
-            mnemonic: "JP NC, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().carry {
-                    return;
-                }
+    });
+    opcodes[0xE5] = Some(Opcode {
+        mnemonic: "PUSH HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| push(cpu, bus, RegisterWide::HL)),
+    });
+    opcodes[0xE6] = Some(Opcode {
+        mnemonic: "AND A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let result = a & imm;
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(true),
+                carry: Some(false),
+            });
+        })
+    });
+    opcodes[0xE7] = Some(Opcode {
+        mnemonic: "RST $20",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x20)),
+    });
+    opcodes[0xE8] = Some(Opcode {
+        mnemonic: "ADD SP, e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let sp = cpu.read_register_wide(RegisterWide::SP);
+
+            // Unintuitively, even though we're adding to a 16 bit integer, the half-carry
+            // flag should be based on the low byte i.e. set when carry occurs from bit 3
+            // to bit 4.
+            //
+            // See:
+            // https://stackoverflow.com/questions/57958631/game-boy-half-carry-flag-and-16-bit-instructions-especially-opcode-0xe8/57978555#57978555
+            let half_carried = util::half_carried_add8(sp as u8, imm);
+
+            // Similar to the half-carry, for the carry we need to look at only the low byte
+            let sp_low_byte = (sp & 0xFF) as u8;
+            let (_, carried) = sp_low_byte.overflowing_add(imm);
+
+            let sp = sp.wrapping_add_signed((imm as i8).into());
 
-                jump(cpu, memory);
-            }),
+            cpu.write_register_wide(RegisterWide::SP, sp);
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(half_carried),
+                carry: Some(carried),
+            });
+            // The addition is done a byte at a time
+            bus.tick();
+            bus.tick();
+        })
+    });
+    opcodes[0xE9] = Some(Opcode {
+        mnemonic: "JP HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            cpu.write_register_wide(RegisterWide::PC, hl);
         }),
-        0xD4 => Some(Opcode {
-            mnemonic: "CALL NC, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if cpu.read_flags().carry {
-                    return;
-                }
+    });
+    opcodes[0xEA] = Some(Opcode {
+        mnemonic: "LD [a16], A",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let lsb = bus.read(Address(pc - 2));
+            let msb = bus.read(Address(pc - 1));
+            let address = util::u8_to_u16(msb, lsb);
+            let a = cpu.read_register(Register::A);
+            bus.write(Address(address), a);
+        }),
+    });
+    opcodes[0xEE] = Some(Opcode {
+        mnemonic: "XOR A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let result = a ^ imm;
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(false),
+            });
+        })
+    });
+    opcodes[0xEF] = Some(Opcode {
+        mnemonic: "RST $28",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x28)),
+    });
+    opcodes[0xF0] = Some(Opcode {
+        mnemonic: "LDH A, [a8]",
+        size_bytes: 2,
+        operand: Operand::A8,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let address = u8_to_u16(0xFF, imm);
+            let value = bus.read(Address(address));
+            cpu.write_register(Register::A, value);
+        }),
+    });
+    opcodes[0xF1] = Some(Opcode {
+        mnemonic: "POP AF",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| pop(cpu, bus, RegisterWide::AF)),
+    });
+    opcodes[0xF2] = Some(Opcode {
+        mnemonic: "LD A, [C]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let c = cpu.read_register(Register::C);
+            let address = util::u8_to_u16(0xFF, c);
+            let value = bus.read(Address(address));
+            cpu.write_register(Register::A, value);
+        })
+    });
+    opcodes[0xF3] = Some(Opcode {
+        mnemonic: "DI",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            cpu.request_ime_disable();
+        })
+    });
+    opcodes[0xF5] = Some(Opcode {
+        mnemonic: "PUSH AF",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| push(cpu, bus, RegisterWide::AF)),
+    });
+    opcodes[0xF6] = Some(Opcode {
+        mnemonic: "OR A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let result = a | imm;
+            cpu.write_register(Register::A, result);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(false),
+                half_carry: Some(false),
+                carry: Some(false),
+            });
+        }),
+    });
+    opcodes[0xF7] = Some(Opcode {
+        mnemonic: "RST $30",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x30)),
+    });
+    opcodes[0xF8] = Some(Opcode {
+        mnemonic: "LD HL, SP + e8",
+        size_bytes: 2,
+        operand: Operand::E8,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let sp = cpu.read_register_wide(RegisterWide::SP);
 
-                call_a16(cpu, memory);
-            }),
-        }),
-        0xD5 => Some(Opcode {
-            mnemonic: "PUSH DE".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| push(cpu, memory, RegisterWide::DE)),
-        }),
-        0xD6 => Some(Opcode {
-            mnemonic: "SUB A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let (result, overflowed) = a.overflowing_sub(imm);
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(true),
-                    half_carry: Some(half_carried_sub8(a, imm)),
-                    carry: Some(overflowed),
-                });
-            })
-        }),
-        0xD7 => Some(Opcode {
-            mnemonic: "RST $10".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x10)),
-        }),
-        0xD8 => Some(Opcode {
-            mnemonic: "RET C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().carry {
-                    return;
-                }
+            // Unintuitively, even though we're adding to a 16 bit integer, the half-carry
+            // flag should be based on the low byte i.e. set when carry occurs from bit 3
+            // to bit 4.
+            //
+            // See:
+            // https://stackoverflow.com/questions/57958631/game-boy-half-carry-flag-and-16-bit-instructions-especially-opcode-0xe8/57978555#57978555
+            let half_carried = util::half_carried_add8(sp as u8, imm);
 
-                pop(cpu, memory, RegisterWide::PC);
-            }),
-        }),
-        0xD9 => Some(Opcode {
-            mnemonic: "RETI ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                cpu.request_ime_enable();
-                pop(cpu, memory, RegisterWide::PC);
-            })
-        }),
-        0xDA => Some(Opcode {
-            mnemonic: "JP C, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().carry {
-                    return;
-                }
+            // Similar to the half-carry, for the carry we need to look at only the low byte
+            let sp_low_byte = (sp & 0xFF) as u8;
+            let (_, carried) = sp_low_byte.overflowing_add(imm);
+
+            let sp = sp.wrapping_add_signed((imm as i8).into());
 
-                jump(cpu, memory);
-            }),
+            cpu.write_register_wide(RegisterWide::HL, sp);
+            cpu.write_flags(WriteFlags {
+                zero: Some(false),
+                subtract: Some(false),
+                half_carry: Some(half_carried),
+                carry: Some(carried),
+            });
+            bus.tick();
+        })
+    });
+    opcodes[0xF9] = Some(Opcode {
+        mnemonic: "LD SP, HL",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let hl = cpu.read_register_wide(RegisterWide::HL);
+            cpu.write_register_wide(RegisterWide::SP, hl);
+            bus.tick();
         }),
-        0xDC => Some(Opcode {
-            mnemonic: "CALL C, a16".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                if !cpu.read_flags().carry {
-                    return;
-                }
+    });
+    opcodes[0xFA] = Some(Opcode {
+        mnemonic: "LD A, [a16]",
+        size_bytes: 3,
+        operand: Operand::A16,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let lsb = bus.read(Address(pc - 2));
+            let msb = bus.read(Address(pc - 1));
+            let address = util::u8_to_u16(msb, lsb);
+            let value = bus.read(Address(address));
+            cpu.write_register(Register::A, value);
+        })
+    });
+    opcodes[0xFB] = Some(Opcode {
+        mnemonic: "EI",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(1),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| {
+            cpu.request_ime_enable();
+        })
+    });
+    opcodes[0xFE] = Some(Opcode {
+        mnemonic: "CP A, n8",
+        size_bytes: 2,
+        operand: Operand::N8,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| {
+            let a = cpu.read_register(Register::A);
+            let pc = cpu.read_register_wide(RegisterWide::PC);
+            let imm = bus.read(Address(pc - 1));
+            let (result, overflowed) = a.overflowing_sub(imm);
+            cpu.write_flags(WriteFlags {
+                zero: Some(result == 0),
+                subtract: Some(true),
+                half_carry: Some(half_carried_sub8(a, imm)),
+                carry: Some(overflowed),
+            });
+        }),
+    });
+    opcodes[0xFF] = Some(Opcode {
+        mnemonic: "RST $38",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| rst(cpu, bus, 0x38)),
+    });
 
-                call_a16(cpu, memory);
-            }),
-        }),
-        0xDE => Some(Opcode {
-            mnemonic: "SBC A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-
-                let (mut result, mut overflowed) = a.overflowing_sub(imm);
-                let mut half_carried = util::half_carried_sub8(a, imm);
-                
-                if cpu.read_flags().carry {
-                    half_carried |= util::half_carried_sub8(result, 1);
-                    let (carry_result, carry_overflowed) = result.overflowing_sub(1);
-                    overflowed |= carry_overflowed;
-                    result = carry_result;
-                }
+    opcodes
+};
 
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(true),
-                    half_carry: Some(half_carried),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0xDF => Some(Opcode {
-            mnemonic: "RST $18".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x18)),
-        }),
-        0xE0 => Some(Opcode {
-            mnemonic: "LDH [a8], A".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let address = u8_to_u16(0xFF, imm);
-                let a = cpu.read_register(Register::A);
-                memory.write(Address(address), a);
-            }),
-        }),
-        0xE1 => Some(Opcode {
-            mnemonic: "POP HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| pop(cpu, memory, RegisterWide::HL)),
-        }),
-        0xE2 => Some(Opcode {
-            mnemonic: "LD [C], A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let c = cpu.read_register(Register::C);
-                let address = Address(util::u8_to_u16(0xFF, c));
-                memory.write(address, a);
-            }),
-        }),
-        0xE5 => Some(Opcode {
-            mnemonic: "PUSH HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| push(cpu, memory, RegisterWide::HL)),
-        }),
-        0xE6 => Some(Opcode {
-            mnemonic: "AND A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let result = a & imm;
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(true),
-                    carry: Some(false),
-                });
-            })
-        }),
-        0xE7 => Some(Opcode {
-            mnemonic: "RST $20".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x20)),
-        }),
-        0xE8 => Some(Opcode {
-            mnemonic: "ADD SP, e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let sp = cpu.read_register_wide(RegisterWide::SP);
-
-                // Unintuitively, even though we're adding to a 16 bit integer, the half-carry
-                // flag should be based on the low byte i.e. set when carry occurs from bit 3
-                // to bit 4.
-                //
-                // See:
-                // https://stackoverflow.com/questions/57958631/game-boy-half-carry-flag-and-16-bit-instructions-especially-opcode-0xe8/57978555#57978555
-                let half_carried = util::half_carried_add8(sp as u8, imm);
-
-                // Similar to the half-carry, for the carry we need to look at only the low byte
-                let sp_low_byte = (sp & 0xFF) as u8;
-                let (_, carried) = sp_low_byte.overflowing_add(imm);
-
-                let sp = sp.wrapping_add_signed((imm as i8).into());
-
-                cpu.write_register_wide(RegisterWide::SP, sp);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried),
-                    carry: Some(carried),
-                });
-            })
-        }),
-        0xE9 => Some(Opcode {
-            mnemonic: "JP HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                cpu.write_register_wide(RegisterWide::PC, hl);
-            }),
-        }),
-        0xEA => Some(Opcode {
-            mnemonic: "LD [a16], A".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let lsb = memory.read(Address(pc - 2));
-                let msb = memory.read(Address(pc - 1));
-                let address = util::u8_to_u16(msb, lsb);
-                let a = cpu.read_register(Register::A);
-                memory.write(Address(address), a);
-            }),
-        }),
-        0xEE => Some(Opcode {
-            mnemonic: "XOR A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let result = a ^ imm;
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(false),
-                });
-            })
-        }),
-        0xEF => Some(Opcode {
-            mnemonic: "RST $28".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x28)),
-        }),
-        0xF0 => Some(Opcode {
-            mnemonic: "LDH A, [a8]".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let address = u8_to_u16(0xFF, imm);
-                let value = memory.read(Address(address));
-                cpu.write_register(Register::A, value);
-            }),
-        }),
-        0xF1 => Some(Opcode {
-            mnemonic: "POP AF".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| pop(cpu, memory, RegisterWide::AF)),
-        }),
-        0xF2 => Some(Opcode {
-            mnemonic: "LD A, [C]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let c = cpu.read_register(Register::C);
-                let address = util::u8_to_u16(0xFF, c);
-                let value = memory.read(Address(address));
-                cpu.write_register(Register::A, value);
-            })
-        }),
-        0xF3 => Some(Opcode {
-            mnemonic: "DI".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                cpu.request_ime_disable();
-            })
-        }),
-        0xF5 => Some(Opcode {
-            mnemonic: "PUSH AF".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| push(cpu, memory, RegisterWide::AF)),
-        }),
-        0xF6 => Some(Opcode {
-            mnemonic: "OR A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let result = a | imm;
-                cpu.write_register(Register::A, result);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(false),
-                    half_carry: Some(false),
-                    carry: Some(false),
-                });
-            }),
-        }),
-        0xF7 => Some(Opcode {
-            mnemonic: "RST $30".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x30)),
-        }),
-        0xF8 => Some(Opcode {
-            mnemonic: "LD HL, SP + e8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let sp = cpu.read_register_wide(RegisterWide::SP);
-
-                // Unintuitively, even though we're adding to a 16 bit integer, the half-carry
-                // flag should be based on the low byte i.e. set when carry occurs from bit 3
-                // to bit 4.
-                //
-                // See:
-                // https://stackoverflow.com/questions/57958631/game-boy-half-carry-flag-and-16-bit-instructions-especially-opcode-0xe8/57978555#57978555
-                let half_carried = util::half_carried_add8(sp as u8, imm);
-
-                // Similar to the half-carry, for the carry we need to look at only the low byte
-                let sp_low_byte = (sp & 0xFF) as u8;
-                let (_, carried) = sp_low_byte.overflowing_add(imm);
-
-                let sp = sp.wrapping_add_signed((imm as i8).into());
-
-                cpu.write_register_wide(RegisterWide::HL, sp);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(false),
-                    subtract: Some(false),
-                    half_carry: Some(half_carried),
-                    carry: Some(carried),
-                });
-            })
-        }),
-        0xF9 => Some(Opcode {
-            mnemonic: "LD SP, HL".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                let hl = cpu.read_register_wide(RegisterWide::HL);
-                cpu.write_register_wide(RegisterWide::SP, hl);
-            }),
-        }),
-        0xFA => Some(Opcode {
-            mnemonic: "LD A, [a16]".to_string(),
-            size_bytes: 3,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let lsb = memory.read(Address(pc - 2));
-                let msb = memory.read(Address(pc - 1));
-                let address = util::u8_to_u16(msb, lsb);
-                let value = memory.read(Address(address));
-                cpu.write_register(Register::A, value);
-            })
-        }),
-        0xFB => Some(Opcode {
-            mnemonic: "EI ".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| {
-                cpu.request_ime_enable();
-            })
-        }),
-        0xFE => Some(Opcode {
-            mnemonic: "CP A, n8".to_string(),
-            size_bytes: 2,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| {
-                let a = cpu.read_register(Register::A);
-                let pc = cpu.read_register_wide(RegisterWide::PC);
-                let imm = memory.read(Address(pc - 1));
-                let (result, overflowed) = a.overflowing_sub(imm);
-                cpu.write_flags(WriteFlags {
-                    zero: Some(result == 0),
-                    subtract: Some(true),
-                    half_carry: Some(half_carried_sub8(a, imm)),
-                    carry: Some(overflowed),
-                });
-            }),
-        }),
-        0xFF => Some(Opcode {
-            mnemonic: "RST $38".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| rst(cpu, memory, 0x38)),
-        }),
-        _ => None,
-    }
-}
+static PREFIXED_OPCODES: [Option<Opcode>; 256] = {
+    let mut opcodes = [const { None }; 256];
+    opcodes[0x00] = Some(Opcode {
+        mnemonic: "RLC B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::B)),
+    });
+    opcodes[0x01] = Some(Opcode {
+        mnemonic: "RLC C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::C)),
+    });
+    opcodes[0x02] = Some(Opcode {
+        mnemonic: "RLC D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::D)),
+    });
+    opcodes[0x03] = Some(Opcode {
+        mnemonic: "RLC E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::E)),
+    });
+    opcodes[0x04] = Some(Opcode {
+        mnemonic: "RLC H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::H)),
+    });
+    opcodes[0x05] = Some(Opcode {
+        mnemonic: "RLC L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::L)),
+    });
+    opcodes[0x06] = Some(Opcode {
+        mnemonic: "RLC [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x07] = Some(Opcode {
+        mnemonic: "RLC A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::A)),
+    });
+    opcodes[0x08] = Some(Opcode {
+        mnemonic: "RRC B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::B)),
+    });
+    opcodes[0x09] = Some(Opcode {
+        mnemonic: "RRC C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::C)),
+    });
+    opcodes[0x0A] = Some(Opcode {
+        mnemonic: "RRC D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::D)),
+    });
+    opcodes[0x0B] = Some(Opcode {
+        mnemonic: "RRC E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::E)),
+    });
+    opcodes[0x0C] = Some(Opcode {
+        mnemonic: "RRC H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::H)),
+    });
+    opcodes[0x0D] = Some(Opcode {
+        mnemonic: "RRC L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::L)),
+    });
+    opcodes[0x0E] = Some(Opcode {
+        mnemonic: "RRC [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x0F] = Some(Opcode {
+        mnemonic: "RRC A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::A)),
+    });
+    opcodes[0x10] = Some(Opcode {
+        mnemonic: "RL B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::B)),
+    });
+    opcodes[0x11] = Some(Opcode {
+        mnemonic: "RL C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::C)),
+    });
+    opcodes[0x12] = Some(Opcode {
+        mnemonic: "RL D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::D)),
+    });
+    opcodes[0x13] = Some(Opcode {
+        mnemonic: "RL E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::E)),
+    });
+    opcodes[0x14] = Some(Opcode {
+        mnemonic: "RL H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::H)),
+    });
+    opcodes[0x15] = Some(Opcode {
+        mnemonic: "RL L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::L)),
+    });
+    opcodes[0x16] = Some(Opcode {
+        mnemonic: "RL [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x17] = Some(Opcode {
+        mnemonic: "RL A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::A)),
+    });
+    opcodes[0x18] = Some(Opcode {
+        mnemonic: "RR B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::B)),
+    });
+    opcodes[0x19] = Some(Opcode {
+        mnemonic: "RR C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::C)),
+    });
+    opcodes[0x1A] = Some(Opcode {
+        mnemonic: "RR D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::D)),
+    });
+    opcodes[0x1B] = Some(Opcode {
+        mnemonic: "RR E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::E)),
+    });
+    opcodes[0x1C] = Some(Opcode {
+        mnemonic: "RR H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::H)),
+    });
+    opcodes[0x1D] = Some(Opcode {
+        mnemonic: "RR L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::L)),
+    });
+    opcodes[0x1E] = Some(Opcode {
+        mnemonic: "RR [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x1F] = Some(Opcode {
+        mnemonic: "RR A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::A)),
+    });
+    opcodes[0x20] = Some(Opcode {
+        mnemonic: "SLA B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::B)),
+    });
+    opcodes[0x21] = Some(Opcode {
+        mnemonic: "SLA C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::C)),
+    });
+    opcodes[0x22] = Some(Opcode {
+        mnemonic: "SLA D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::D)),
+    });
+    opcodes[0x23] = Some(Opcode {
+        mnemonic: "SLA E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::E)),
+    });
+    opcodes[0x24] = Some(Opcode {
+        mnemonic: "SLA H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::H)),
+    });
+    opcodes[0x25] = Some(Opcode {
+        mnemonic: "SLA L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::L)),
+    });
+    opcodes[0x26] = Some(Opcode {
+        mnemonic: "SLA [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x27] = Some(Opcode {
+        mnemonic: "SLA A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sla_r8(cpu, Register::A)),
+    });
+    opcodes[0x28] = Some(Opcode {
+        mnemonic: "SRA B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::B)),
+    });
+    opcodes[0x29] = Some(Opcode {
+        mnemonic: "SRA C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::C)),
+    });
+    opcodes[0x2A] = Some(Opcode {
+        mnemonic: "SRA D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::D)),
+    });
+    opcodes[0x2B] = Some(Opcode {
+        mnemonic: "SRA E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::E)),
+    });
+    opcodes[0x2C] = Some(Opcode {
+        mnemonic: "SRA H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::H)),
+    });
+    opcodes[0x2D] = Some(Opcode {
+        mnemonic: "SRA L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::L)),
+    });
+    opcodes[0x2E] = Some(Opcode {
+        mnemonic: "SRA [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x2F] = Some(Opcode {
+        mnemonic: "SRA A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu, _| sra_r8(cpu, Register::A)),
+    });
+    opcodes[0x30] = Some(Opcode {
+        mnemonic: "SWAP B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::B)),
+    });
+    opcodes[0x31] = Some(Opcode {
+        mnemonic: "SWAP C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::C)),
+    });
+    opcodes[0x32] = Some(Opcode {
+        mnemonic: "SWAP D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::D)),
+    });
+    opcodes[0x33] = Some(Opcode {
+        mnemonic: "SWAP E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::E)),
+    });
+    opcodes[0x34] = Some(Opcode {
+        mnemonic: "SWAP H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::H)),
+    });
+    opcodes[0x35] = Some(Opcode {
+        mnemonic: "SWAP L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::L)),
+    });
+    opcodes[0x36] = Some(Opcode {
+        mnemonic: "SWAP [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x37] = Some(Opcode {
+        mnemonic: "SWAP A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::A)),
+    });
+    opcodes[0x38] = Some(Opcode {
+        mnemonic: "SRL B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::B)),
+    });
+    opcodes[0x39] = Some(Opcode {
+        mnemonic: "SRL C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::C)),
+    });
+    opcodes[0x3A] = Some(Opcode {
+        mnemonic: "SRL D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::D)),
+    });
+    opcodes[0x3B] = Some(Opcode {
+        mnemonic: "SRL E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::E)),
+    });
+    opcodes[0x3C] = Some(Opcode {
+        mnemonic: "SRL H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::H)),
+    });
+    opcodes[0x3D] = Some(Opcode {
+        mnemonic: "SRL L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::L)),
+    });
+    opcodes[0x3E] = Some(Opcode {
+        mnemonic: "SRL [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: None,
+    });
+    opcodes[0x3F] = Some(Opcode {
+        mnemonic: "SRL A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::A)),
+    });
+    opcodes[0x40] = Some(Opcode {
+        mnemonic: "BIT 0, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::B)),
+    });
+    opcodes[0x41] = Some(Opcode {
+        mnemonic: "BIT 0, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::C)),
+    });
+    opcodes[0x42] = Some(Opcode {
+        mnemonic: "BIT 0, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::D)),
+    });
+    opcodes[0x43] = Some(Opcode {
+        mnemonic: "BIT 0, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::E)),
+    });
+    opcodes[0x44] = Some(Opcode {
+        mnemonic: "BIT 0, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::H)),
+    });
+    opcodes[0x45] = Some(Opcode {
+        mnemonic: "BIT 0, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::L)),
+    });
+    opcodes[0x46] = Some(Opcode {
+        mnemonic: "BIT 0, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Zero)),
+    });
+    opcodes[0x47] = Some(Opcode {
+        mnemonic: "BIT 0, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::A)),
+    });
+    opcodes[0x48] = Some(Opcode {
+        mnemonic: "BIT 1, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::B)),
+    });
+    opcodes[0x49] = Some(Opcode {
+        mnemonic: "BIT 1, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::C)),
+    });
+    opcodes[0x4A] = Some(Opcode {
+        mnemonic: "BIT 1, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::D)),
+    });
+    opcodes[0x4B] = Some(Opcode {
+        mnemonic: "BIT 1, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::E)),
+    });
+    opcodes[0x4C] = Some(Opcode {
+        mnemonic: "BIT 1, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::H)),
+    });
+    opcodes[0x4D] = Some(Opcode {
+        mnemonic: "BIT 1, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::L)),
+    });
+    opcodes[0x4E] = Some(Opcode {
+        mnemonic: "BIT 1, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::One)),
+    });
+    opcodes[0x4F] = Some(Opcode {
+        mnemonic: "BIT 1, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::A)),
+    });
+    opcodes[0x50] = Some(Opcode {
+        mnemonic: "BIT 2, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::B)),
+    });
+    opcodes[0x51] = Some(Opcode {
+        mnemonic: "BIT 2, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::C)),
+    });
+    opcodes[0x52] = Some(Opcode {
+        mnemonic: "BIT 2, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::D)),
+    });
+    opcodes[0x53] = Some(Opcode {
+        mnemonic: "BIT 2, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::E)),
+    });
+    opcodes[0x54] = Some(Opcode {
+        mnemonic: "BIT 2, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::H)),
+    });
+    opcodes[0x55] = Some(Opcode {
+        mnemonic: "BIT 2, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::L)),
+    });
+    opcodes[0x56] = Some(Opcode {
+        mnemonic: "BIT 2, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Two)),
+    });
+    opcodes[0x57] = Some(Opcode {
+        mnemonic: "BIT 2, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::A)),
+    });
+    opcodes[0x58] = Some(Opcode {
+        mnemonic: "BIT 3, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::B)),
+    });
+    opcodes[0x59] = Some(Opcode {
+        mnemonic: "BIT 3, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::C)),
+    });
+    opcodes[0x5A] = Some(Opcode {
+        mnemonic: "BIT 3, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::D)),
+    });
+    opcodes[0x5B] = Some(Opcode {
+        mnemonic: "BIT 3, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::E)),
+    });
+    opcodes[0x5C] = Some(Opcode {
+        mnemonic: "BIT 3, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::H)),
+    });
+    opcodes[0x5D] = Some(Opcode {
+        mnemonic: "BIT 3, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::L)),
+    });
+    opcodes[0x5E] = Some(Opcode {
+        mnemonic: "BIT 3, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Three)),
+    });
+    opcodes[0x5F] = Some(Opcode {
+        mnemonic: "BIT 3, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::A)),
+    });
+    opcodes[0x60] = Some(Opcode {
+        mnemonic: "BIT 4, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::B)),
+    });
+    opcodes[0x61] = Some(Opcode {
+        mnemonic: "BIT 4, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::C)),
+    });
+    opcodes[0x62] = Some(Opcode {
+        mnemonic: "BIT 4, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::D)),
+    });
+    opcodes[0x63] = Some(Opcode {
+        mnemonic: "BIT 4, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::E)),
+    });
+    opcodes[0x64] = Some(Opcode {
+        mnemonic: "BIT 4, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::H)),
+    });
+    opcodes[0x65] = Some(Opcode {
+        mnemonic: "BIT 4, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::L)),
+    });
+    opcodes[0x66] = Some(Opcode {
+        mnemonic: "BIT 4, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Four)),
+    });
+    opcodes[0x67] = Some(Opcode {
+        mnemonic: "BIT 4, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::A)),
+    });
+    opcodes[0x68] = Some(Opcode {
+        mnemonic: "BIT 5, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::B)),
+    });
+    opcodes[0x69] = Some(Opcode {
+        mnemonic: "BIT 5, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::C)),
+    });
+    opcodes[0x6A] = Some(Opcode {
+        mnemonic: "BIT 5, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::D)),
+    });
+    opcodes[0x6B] = Some(Opcode {
+        mnemonic: "BIT 5, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::E)),
+    });
+    opcodes[0x6C] = Some(Opcode {
+        mnemonic: "BIT 5, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::H)),
+    });
+    opcodes[0x6D] = Some(Opcode {
+        mnemonic: "BIT 5, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::L)),
+    });
+    opcodes[0x6E] = Some(Opcode {
+        mnemonic: "BIT 5, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Five)),
+    });
+    opcodes[0x6F] = Some(Opcode {
+        mnemonic: "BIT 5, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::A)),
+    });
+    opcodes[0x70] = Some(Opcode {
+        mnemonic: "BIT 6, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::B)),
+    });
+    opcodes[0x71] = Some(Opcode {
+        mnemonic: "BIT 6, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::C)),
+    });
+    opcodes[0x72] = Some(Opcode {
+        mnemonic: "BIT 6, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::D)),
+    });
+    opcodes[0x73] = Some(Opcode {
+        mnemonic: "BIT 6, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::E)),
+    });
+    opcodes[0x74] = Some(Opcode {
+        mnemonic: "BIT 6, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::H)),
+    });
+    opcodes[0x75] = Some(Opcode {
+        mnemonic: "BIT 6, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::L)),
+    });
+    opcodes[0x76] = Some(Opcode {
+        mnemonic: "BIT 6, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Six)),
+    });
+    opcodes[0x77] = Some(Opcode {
+        mnemonic: "BIT 6, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::A)),
+    });
+    opcodes[0x78] = Some(Opcode {
+        mnemonic: "BIT 7, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::B)),
+    });
+    opcodes[0x79] = Some(Opcode {
+        mnemonic: "BIT 7, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::C)),
+    });
+    opcodes[0x7A] = Some(Opcode {
+        mnemonic: "BIT 7, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::D)),
+    });
+    opcodes[0x7B] = Some(Opcode {
+        mnemonic: "BIT 7, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::E)),
+    });
+    opcodes[0x7C] = Some(Opcode {
+        mnemonic: "BIT 7, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::H)),
+    });
+    opcodes[0x7D] = Some(Opcode {
+        mnemonic: "BIT 7, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::L)),
+    });
+    opcodes[0x7E] = Some(Opcode {
+        mnemonic: "BIT 7, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(3),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| bit_indirect_hl(cpu, bus, Bit::Seven)),
+    });
+    opcodes[0x7F] = Some(Opcode {
+        mnemonic: "BIT 7, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::A)),
+    });
+    opcodes[0x80] = Some(Opcode {
+        mnemonic: "RES 0, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::B)),
+    });
+    opcodes[0x81] = Some(Opcode {
+        mnemonic: "RES 0, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::C)),
+    });
+    opcodes[0x82] = Some(Opcode {
+        mnemonic: "RES 0, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::D)),
+    });
+    opcodes[0x83] = Some(Opcode {
+        mnemonic: "RES 0, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::E)),
+    });
+    opcodes[0x84] = Some(Opcode {
+        mnemonic: "RES 0, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::H)),
+    });
+    opcodes[0x85] = Some(Opcode {
+        mnemonic: "RES 0, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::L)),
+    });
+    opcodes[0x86] = Some(Opcode {
+        mnemonic: "RES 0, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Zero)),
+    });
+    opcodes[0x87] = Some(Opcode {
+        mnemonic: "RES 0, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::A)),
+    });
+    opcodes[0x88] = Some(Opcode {
+        mnemonic: "RES 1, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::B)),
+    });
+    opcodes[0x89] = Some(Opcode {
+        mnemonic: "RES 1, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::C)),
+    });
+    opcodes[0x8A] = Some(Opcode {
+        mnemonic: "RES 1, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::D)),
+    });
+    opcodes[0x8B] = Some(Opcode {
+        mnemonic: "RES 1, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::E)),
+    });
+    opcodes[0x8C] = Some(Opcode {
+        mnemonic: "RES 1, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::H)),
+    });
+    opcodes[0x8D] = Some(Opcode {
+        mnemonic: "RES 1, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::L)),
+    });
+    opcodes[0x8E] = Some(Opcode {
+        mnemonic: "RES 1, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::One)),
+    });
+    opcodes[0x8F] = Some(Opcode {
+        mnemonic: "RES 1, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::A)),
+    });
+    opcodes[0x90] = Some(Opcode {
+        mnemonic: "RES 2, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::B)),
+    });
+    opcodes[0x91] = Some(Opcode {
+        mnemonic: "RES 2, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::C)),
+    });
+    opcodes[0x92] = Some(Opcode {
+        mnemonic: "RES 2, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::D)),
+    });
+    opcodes[0x93] = Some(Opcode {
+        mnemonic: "RES 2, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::E)),
+    });
+    opcodes[0x94] = Some(Opcode {
+        mnemonic: "RES 2, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::H)),
+    });
+    opcodes[0x95] = Some(Opcode {
+        mnemonic: "RES 2, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::L)),
+    });
+    opcodes[0x96] = Some(Opcode {
+        mnemonic: "RES 2, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Two)),
+    });
+    opcodes[0x97] = Some(Opcode {
+        mnemonic: "RES 2, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::A)),
+    });
+    opcodes[0x98] = Some(Opcode {
+        mnemonic: "RES 3, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::B)),
+    });
+    opcodes[0x99] = Some(Opcode {
+        mnemonic: "RES 3, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::C)),
+    });
+    opcodes[0x9A] = Some(Opcode {
+        mnemonic: "RES 3, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::D)),
+    });
+    opcodes[0x9B] = Some(Opcode {
+        mnemonic: "RES 3, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::E)),
+    });
+    opcodes[0x9C] = Some(Opcode {
+        mnemonic: "RES 3, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::H)),
+    });
+    opcodes[0x9D] = Some(Opcode {
+        mnemonic: "RES 3, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::L)),
+    });
+    opcodes[0x9E] = Some(Opcode {
+        mnemonic: "RES 3, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Three)),
+    });
+    opcodes[0x9F] = Some(Opcode {
+        mnemonic: "RES 3, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::A)),
+    });
+    opcodes[0xA0] = Some(Opcode {
+        mnemonic: "RES 4, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::B)),
+    });
+    opcodes[0xA1] = Some(Opcode {
+        mnemonic: "RES 4, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::C)),
+    });
+    opcodes[0xA2] = Some(Opcode {
+        mnemonic: "RES 4, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::D)),
+    });
+    opcodes[0xA3] = Some(Opcode {
+        mnemonic: "RES 4, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::E)),
+    });
+    opcodes[0xA4] = Some(Opcode {
+        mnemonic: "RES 4, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::H)),
+    });
+    opcodes[0xA5] = Some(Opcode {
+        mnemonic: "RES 4, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::L)),
+    });
+    opcodes[0xA6] = Some(Opcode {
+        mnemonic: "RES 4, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Four)),
+    });
+    opcodes[0xA7] = Some(Opcode {
+        mnemonic: "RES 4, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::A)),
+    });
+    opcodes[0xA8] = Some(Opcode {
+        mnemonic: "RES 5, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::B)),
+    });
+    opcodes[0xA9] = Some(Opcode {
+        mnemonic: "RES 5, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::C)),
+    });
+    opcodes[0xAA] = Some(Opcode {
+        mnemonic: "RES 5, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::D)),
+    });
+    opcodes[0xAB] = Some(Opcode {
+        mnemonic: "RES 5, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::E)),
+    });
+    opcodes[0xAC] = Some(Opcode {
+        mnemonic: "RES 5, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::H)),
+    });
+    opcodes[0xAD] = Some(Opcode {
+        mnemonic: "RES 5, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::L)),
+    });
+    opcodes[0xAE] = Some(Opcode {
+        mnemonic: "RES 5, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Five)),
+    });
+    opcodes[0xAF] = Some(Opcode {
+        mnemonic: "RES 5, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::A)),
+    });
+    opcodes[0xB0] = Some(Opcode {
+        mnemonic: "RES 6, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::B)),
+    });
+    opcodes[0xB1] = Some(Opcode {
+        mnemonic: "RES 6, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::C)),
+    });
+    opcodes[0xB2] = Some(Opcode {
+        mnemonic: "RES 6, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::D)),
+    });
+    opcodes[0xB3] = Some(Opcode {
+        mnemonic: "RES 6, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::E)),
+    });
+    opcodes[0xB4] = Some(Opcode {
+        mnemonic: "RES 6, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::H)),
+    });
+    opcodes[0xB5] = Some(Opcode {
+        mnemonic: "RES 6, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::L)),
+    });
+    opcodes[0xB6] = Some(Opcode {
+        mnemonic: "RES 6, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Six)),
+    });
+    opcodes[0xB7] = Some(Opcode {
+        mnemonic: "RES 6, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::A)),
+    });
+    opcodes[0xB8] = Some(Opcode {
+        mnemonic: "RES 7, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::B)),
+    });
+    opcodes[0xB9] = Some(Opcode {
+        mnemonic: "RES 7, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::C)),
+    });
+    opcodes[0xBA] = Some(Opcode {
+        mnemonic: "RES 7, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::D)),
+    });
+    opcodes[0xBB] = Some(Opcode {
+        mnemonic: "RES 7, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::E)),
+    });
+    opcodes[0xBC] = Some(Opcode {
+        mnemonic: "RES 7, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::H)),
+    });
+    opcodes[0xBD] = Some(Opcode {
+        mnemonic: "RES 7, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::L)),
+    });
+    opcodes[0xBE] = Some(Opcode {
+        mnemonic: "RES 7, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| res_indirect_hl(cpu, bus, Bit::Seven)),
+    });
+    opcodes[0xBF] = Some(Opcode {
+        mnemonic: "RES 7, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::A)),
+    });
+    opcodes[0xC0] = Some(Opcode {
+        mnemonic: "SET 0, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::B)),
+    });
+    opcodes[0xC1] = Some(Opcode {
+        mnemonic: "SET 0, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::C)),
+    });
+    opcodes[0xC2] = Some(Opcode {
+        mnemonic: "SET 0, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::D)),
+    });
+    opcodes[0xC3] = Some(Opcode {
+        mnemonic: "SET 0, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::E)),
+    });
+    opcodes[0xC4] = Some(Opcode {
+        mnemonic: "SET 0, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::H)),
+    });
+    opcodes[0xC5] = Some(Opcode {
+        mnemonic: "SET 0, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::L)),
+    });
+    opcodes[0xC6] = Some(Opcode {
+        mnemonic: "SET 0, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Zero)),
+    });
+    opcodes[0xC7] = Some(Opcode {
+        mnemonic: "SET 0, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::A)),
+    });
+    opcodes[0xC8] = Some(Opcode {
+        mnemonic: "SET 1, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::B)),
+    });
+    opcodes[0xC9] = Some(Opcode {
+        mnemonic: "SET 1, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::C)),
+    });
+    opcodes[0xCA] = Some(Opcode {
+        mnemonic: "SET 1, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::D)),
+    });
+    opcodes[0xCB] = Some(Opcode {
+        mnemonic: "SET 1, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::E)),
+    });
+    opcodes[0xCC] = Some(Opcode {
+        mnemonic: "SET 1, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::H)),
+    });
+    opcodes[0xCD] = Some(Opcode {
+        mnemonic: "SET 1, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::L)),
+    });
+    opcodes[0xCE] = Some(Opcode {
+        mnemonic: "SET 1, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::One)),
+    });
+    opcodes[0xCF] = Some(Opcode {
+        mnemonic: "SET 1, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::A)),
+    });
+    opcodes[0xD0] = Some(Opcode {
+        mnemonic: "SET 2, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::B)),
+    });
+    opcodes[0xD1] = Some(Opcode {
+        mnemonic: "SET 2, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::C)),
+    });
+    opcodes[0xD2] = Some(Opcode {
+        mnemonic: "SET 2, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::D)),
+    });
+    opcodes[0xD3] = Some(Opcode {
+        mnemonic: "SET 2, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::E)),
+    });
+    opcodes[0xD4] = Some(Opcode {
+        mnemonic: "SET 2, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::H)),
+    });
+    opcodes[0xD5] = Some(Opcode {
+        mnemonic: "SET 2, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::L)),
+    });
+    opcodes[0xD6] = Some(Opcode {
+        mnemonic: "SET 2, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Two)),
+    });
+    opcodes[0xD7] = Some(Opcode {
+        mnemonic: "SET 2, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::A)),
+    });
+    opcodes[0xD8] = Some(Opcode {
+        mnemonic: "SET 3, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::B)),
+    });
+    opcodes[0xD9] = Some(Opcode {
+        mnemonic: "SET 3, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::C)),
+    });
+    opcodes[0xDA] = Some(Opcode {
+        mnemonic: "SET 3, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::D)),
+    });
+    opcodes[0xDB] = Some(Opcode {
+        mnemonic: "SET 3, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::E)),
+    });
+    opcodes[0xDC] = Some(Opcode {
+        mnemonic: "SET 3, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::H)),
+    });
+    opcodes[0xDD] = Some(Opcode {
+        mnemonic: "SET 3, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::L)),
+    });
+    opcodes[0xDE] = Some(Opcode {
+        mnemonic: "SET 3, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Three)),
+    });
+    opcodes[0xDF] = Some(Opcode {
+        mnemonic: "SET 3, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::A)),
+    });
+    opcodes[0xE0] = Some(Opcode {
+        mnemonic: "SET 4, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::B)),
+    });
+    opcodes[0xE1] = Some(Opcode {
+        mnemonic: "SET 4, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::C)),
+    });
+    opcodes[0xE2] = Some(Opcode {
+        mnemonic: "SET 4, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::D)),
+    });
+    opcodes[0xE3] = Some(Opcode {
+        mnemonic: "SET 4, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::E)),
+    });
+    opcodes[0xE4] = Some(Opcode {
+        mnemonic: "SET 4, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::H)),
+    });
+    opcodes[0xE5] = Some(Opcode {
+        mnemonic: "SET 4, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::L)),
+    });
+    opcodes[0xE6] = Some(Opcode {
+        mnemonic: "SET 4, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Four)),
+    });
+    opcodes[0xE7] = Some(Opcode {
+        mnemonic: "SET 4, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::A)),
+    });
+    opcodes[0xE8] = Some(Opcode {
+        mnemonic: "SET 5, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::B)),
+    });
+    opcodes[0xE9] = Some(Opcode {
+        mnemonic: "SET 5, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::C)),
+    });
+    opcodes[0xEA] = Some(Opcode {
+        mnemonic: "SET 5, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::D)),
+    });
+    opcodes[0xEB] = Some(Opcode {
+        mnemonic: "SET 5, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::E)),
+    });
+    opcodes[0xEC] = Some(Opcode {
+        mnemonic: "SET 5, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::H)),
+    });
+    opcodes[0xED] = Some(Opcode {
+        mnemonic: "SET 5, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::L)),
+    });
+    opcodes[0xEE] = Some(Opcode {
+        mnemonic: "SET 5, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Five)),
+    });
+    opcodes[0xEF] = Some(Opcode {
+        mnemonic: "SET 5, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::A)),
+    });
+    opcodes[0xF0] = Some(Opcode {
+        mnemonic: "SET 6, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::B)),
+    });
+    opcodes[0xF1] = Some(Opcode {
+        mnemonic: "SET 6, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::C)),
+    });
+    opcodes[0xF2] = Some(Opcode {
+        mnemonic: "SET 6, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::D)),
+    });
+    opcodes[0xF3] = Some(Opcode {
+        mnemonic: "SET 6, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::E)),
+    });
+    opcodes[0xF4] = Some(Opcode {
+        mnemonic: "SET 6, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::H)),
+    });
+    opcodes[0xF5] = Some(Opcode {
+        mnemonic: "SET 6, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::L)),
+    });
+    opcodes[0xF6] = Some(Opcode {
+        mnemonic: "SET 6, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Six)),
+    });
+    opcodes[0xF7] = Some(Opcode {
+        mnemonic: "SET 6, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::A)),
+    });
+    opcodes[0xF8] = Some(Opcode {
+        mnemonic: "SET 7, B",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::B)),
+    });
+    opcodes[0xF9] = Some(Opcode {
+        mnemonic: "SET 7, C",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::C)),
+    });
+    opcodes[0xFA] = Some(Opcode {
+        mnemonic: "SET 7, D",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::D)),
+    });
+    opcodes[0xFB] = Some(Opcode {
+        mnemonic: "SET 7, E",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::E)),
+    });
+    opcodes[0xFC] = Some(Opcode {
+        mnemonic: "SET 7, H",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::H)),
+    });
+    opcodes[0xFD] = Some(Opcode {
+        mnemonic: "SET 7, L",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::L)),
+    });
+    opcodes[0xFE] = Some(Opcode {
+        mnemonic: "SET 7, [HL]",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(4),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, bus: &mut dyn Bus| set_indirect_hl(cpu, bus, Bit::Seven)),
+    });
+    opcodes[0xFF] = Some(Opcode {
+        mnemonic: "SET 7, A",
+        size_bytes: 1,
+        operand: Operand::None,
+        cycles: Cycles::Fixed(2),
+        prefix: false,
+        handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::A)),
+    });
 
-pub fn decode_prefixed(byte: u8) -> Option<Opcode> {
-    match byte {
-        0x00 => Some(Opcode {
-            mnemonic: "RLC B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::B)),
-        }),
-        0x01 => Some(Opcode {
-            mnemonic: "RLC C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::C)),
-        }),
-        0x02 => Some(Opcode {
-            mnemonic: "RLC D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::D)),
-        }),
-        0x03 => Some(Opcode {
-            mnemonic: "RLC E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::E)),
-        }),
-        0x04 => Some(Opcode {
-            mnemonic: "RLC H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::H)),
-        }),
-        0x05 => Some(Opcode {
-            mnemonic: "RLC L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::L)),
-        }),
-        0x06 => Some(Opcode {
-            mnemonic: "RLC [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x07 => Some(Opcode {
-            mnemonic: "RLC A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rlc_r8(cpu, Register::A)),
-        }),
-        0x08 => Some(Opcode {
-            mnemonic: "RRC B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::B)),
-        }),
-        0x09 => Some(Opcode {
-            mnemonic: "RRC C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::C)),
-        }),
-        0x0A => Some(Opcode {
-            mnemonic: "RRC D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::D)),
-        }),
-        0x0B => Some(Opcode {
-            mnemonic: "RRC E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::E)),
-        }),
-        0x0C => Some(Opcode {
-            mnemonic: "RRC H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::H)),
-        }),
-        0x0D => Some(Opcode {
-            mnemonic: "RRC L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::L)),
-        }),
-        0x0E => Some(Opcode {
-            mnemonic: "RRC [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x0F => Some(Opcode {
-            mnemonic: "RRC A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rrc_r8(cpu, Register::A)),
-        }),
-        0x10 => Some(Opcode {
-            mnemonic: "RL B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::B)),
-        }),
-        0x11 => Some(Opcode {
-            mnemonic: "RL C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::C)),
-        }),
-        0x12 => Some(Opcode {
-            mnemonic: "RL D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::D)),
-        }),
-        0x13 => Some(Opcode {
-            mnemonic: "RL E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::E)),
-        }),
-        0x14 => Some(Opcode {
-            mnemonic: "RL H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::H)),
-        }),
-        0x15 => Some(Opcode {
-            mnemonic: "RL L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::L)),
-        }),
-        0x16 => Some(Opcode {
-            mnemonic: "RL [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x17 => Some(Opcode {
-            mnemonic: "RL A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rl_r8(cpu, Register::A)),
-        }),
-        0x18 => Some(Opcode {
-            mnemonic: "RR B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::B)),
-        }),
-        0x19 => Some(Opcode {
-            mnemonic: "RR C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::C)),
-        }),
-        0x1A => Some(Opcode {
-            mnemonic: "RR D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::D)),
-        }),
-        0x1B => Some(Opcode {
-            mnemonic: "RR E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::E)),
-        }),
-        0x1C => Some(Opcode {
-            mnemonic: "RR H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::H)),
-        }),
-        0x1D => Some(Opcode {
-            mnemonic: "RR L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::L)),
-        }),
-        0x1E => Some(Opcode {
-            mnemonic: "RR [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x1F => Some(Opcode {
-            mnemonic: "RR A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| rr_r8(cpu, Register::A)),
-        }),
-        0x20 => Some(Opcode {
-            mnemonic: "SLA B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::B)),
-        }),
-        0x21 => Some(Opcode {
-            mnemonic: "SLA C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::C)),
-        }),
-        0x22 => Some(Opcode {
-            mnemonic: "SLA D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::D)),
-        }),
-        0x23 => Some(Opcode {
-            mnemonic: "SLA E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::E)),
-        }),
-        0x24 => Some(Opcode {
-            mnemonic: "SLA H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::H)),
-        }),
-        0x25 => Some(Opcode {
-            mnemonic: "SLA L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::L)),
-        }),
-        0x26 => Some(Opcode {
-            mnemonic: "SLA [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x27 => Some(Opcode {
-            mnemonic: "SLA A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sla_r8(cpu, Register::A)),
-        }),
-        0x28 => Some(Opcode {
-            mnemonic: "SRA B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::B)),
-        }),
-        0x29 => Some(Opcode {
-            mnemonic: "SRA C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::C)),
-        }),
-        0x2A => Some(Opcode {
-            mnemonic: "SRA D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::D)),
-        }),
-        0x2B => Some(Opcode {
-            mnemonic: "SRA E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::E)),
-        }),
-        0x2C => Some(Opcode {
-            mnemonic: "SRA H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::H)),
-        }),
-        0x2D => Some(Opcode {
-            mnemonic: "SRA L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::L)),
-        }),
-        0x2E => Some(Opcode {
-            mnemonic: "SRA [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x2F => Some(Opcode {
-            mnemonic: "SRA A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu, _| sra_r8(cpu, Register::A)),
-        }),
-        0x30 => Some(Opcode {
-            mnemonic: "SWAP B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::B)),
-        }),
-        0x31 => Some(Opcode {
-            mnemonic: "SWAP C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::C)),
-        }),
-        0x32 => Some(Opcode {
-            mnemonic: "SWAP D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::D)),
-        }),
-        0x33 => Some(Opcode {
-            mnemonic: "SWAP E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::E)),
-        }),
-        0x34 => Some(Opcode {
-            mnemonic: "SWAP H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::H)),
-        }),
-        0x35 => Some(Opcode {
-            mnemonic: "SWAP L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::L)),
-        }),
-        0x36 => Some(Opcode {
-            mnemonic: "SWAP [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x37 => Some(Opcode {
-            mnemonic: "SWAP A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| swap_r8(cpu, Register::A)),
-        }),
-        0x38 => Some(Opcode {
-            mnemonic: "SRL B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::B)),
-        }),
-        0x39 => Some(Opcode {
-            mnemonic: "SRL C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::C)),
-        }),
-        0x3A => Some(Opcode {
-            mnemonic: "SRL D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::D)),
-        }),
-        0x3B => Some(Opcode {
-            mnemonic: "SRL E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::E)),
-        }),
-        0x3C => Some(Opcode {
-            mnemonic: "SRL H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::H)),
-        }),
-        0x3D => Some(Opcode {
-            mnemonic: "SRL L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::L)),
-        }),
-        0x3E => Some(Opcode {
-            mnemonic: "SRL [HL]".to_string(),
-            size_bytes: 1,
-            handler: None,
-        }),
-        0x3F => Some(Opcode {
-            mnemonic: "SRL A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| srl_r8(cpu, Register::A)),
-        }),
-        0x40 => Some(Opcode {
-            mnemonic: "BIT 0, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::B)),
-        }),
-        0x41 => Some(Opcode {
-            mnemonic: "BIT 0, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::C)),
-        }),
-        0x42 => Some(Opcode {
-            mnemonic: "BIT 0, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::D)),
-        }),
-        0x43 => Some(Opcode {
-            mnemonic: "BIT 0, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::E)),
-        }),
-        0x44 => Some(Opcode {
-            mnemonic: "BIT 0, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::H)),
-        }),
-        0x45 => Some(Opcode {
-            mnemonic: "BIT 0, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::L)),
-        }),
-        0x46 => Some(Opcode {
-            mnemonic: "BIT 0, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Zero)),
-        }),
-        0x47 => Some(Opcode {
-            mnemonic: "BIT 0, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Zero, Register::A)),
-        }),
-        0x48 => Some(Opcode {
-            mnemonic: "BIT 1, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::B)),
-        }),
-        0x49 => Some(Opcode {
-            mnemonic: "BIT 1, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::C)),
-        }),
-        0x4A => Some(Opcode {
-            mnemonic: "BIT 1, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::D)),
-        }),
-        0x4B => Some(Opcode {
-            mnemonic: "BIT 1, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::E)),
-        }),
-        0x4C => Some(Opcode {
-            mnemonic: "BIT 1, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::H)),
-        }),
-        0x4D => Some(Opcode {
-            mnemonic: "BIT 1, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::L)),
-        }),
-        0x4E => Some(Opcode {
-            mnemonic: "BIT 1, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::One)),
-        }),
-        0x4F => Some(Opcode {
-            mnemonic: "BIT 1, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::One, Register::A)),
-        }),
-        0x50 => Some(Opcode {
-            mnemonic: "BIT 2, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::B)),
-        }),
-        0x51 => Some(Opcode {
-            mnemonic: "BIT 2, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::C)),
-        }),
-        0x52 => Some(Opcode {
-            mnemonic: "BIT 2, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::D)),
-        }),
-        0x53 => Some(Opcode {
-            mnemonic: "BIT 2, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::E)),
-        }),
-        0x54 => Some(Opcode {
-            mnemonic: "BIT 2, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::H)),
-        }),
-        0x55 => Some(Opcode {
-            mnemonic: "BIT 2, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::L)),
-        }),
-        0x56 => Some(Opcode {
-            mnemonic: "BIT 2, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Two)),
-        }),
-        0x57 => Some(Opcode {
-            mnemonic: "BIT 2, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Two, Register::A)),
-        }),
-        0x58 => Some(Opcode {
-            mnemonic: "BIT 3, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::B)),
-        }),
-        0x59 => Some(Opcode {
-            mnemonic: "BIT 3, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::C)),
-        }),
-        0x5A => Some(Opcode {
-            mnemonic: "BIT 3, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::D)),
-        }),
-        0x5B => Some(Opcode {
-            mnemonic: "BIT 3, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::E)),
-        }),
-        0x5C => Some(Opcode {
-            mnemonic: "BIT 3, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::H)),
-        }),
-        0x5D => Some(Opcode {
-            mnemonic: "BIT 3, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::L)),
-        }),
-        0x5E => Some(Opcode {
-            mnemonic: "BIT 3, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Three)),
-        }),
-        0x5F => Some(Opcode {
-            mnemonic: "BIT 3, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Three, Register::A)),
-        }),
-        0x60 => Some(Opcode {
-            mnemonic: "BIT 4, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::B)),
-        }),
-        0x61 => Some(Opcode {
-            mnemonic: "BIT 4, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::C)),
-        }),
-        0x62 => Some(Opcode {
-            mnemonic: "BIT 4, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::D)),
-        }),
-        0x63 => Some(Opcode {
-            mnemonic: "BIT 4, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::E)),
-        }),
-        0x64 => Some(Opcode {
-            mnemonic: "BIT 4, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::H)),
-        }),
-        0x65 => Some(Opcode {
-            mnemonic: "BIT 4, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::L)),
-        }),
-        0x66 => Some(Opcode {
-            mnemonic: "BIT 4, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Four)),
-        }),
-        0x67 => Some(Opcode {
-            mnemonic: "BIT 4, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Four, Register::A)),
-        }),
-        0x68 => Some(Opcode {
-            mnemonic: "BIT 5, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::B)),
-        }),
-        0x69 => Some(Opcode {
-            mnemonic: "BIT 5, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::C)),
-        }),
-        0x6A => Some(Opcode {
-            mnemonic: "BIT 5, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::D)),
-        }),
-        0x6B => Some(Opcode {
-            mnemonic: "BIT 5, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::E)),
-        }),
-        0x6C => Some(Opcode {
-            mnemonic: "BIT 5, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::H)),
-        }),
-        0x6D => Some(Opcode {
-            mnemonic: "BIT 5, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::L)),
-        }),
-        0x6E => Some(Opcode {
-            mnemonic: "BIT 5, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Five)),
-        }),
-        0x6F => Some(Opcode {
-            mnemonic: "BIT 5, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Five, Register::A)),
-        }),
-        0x70 => Some(Opcode {
-            mnemonic: "BIT 6, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::B)),
-        }),
-        0x71 => Some(Opcode {
-            mnemonic: "BIT 6, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::C)),
-        }),
-        0x72 => Some(Opcode {
-            mnemonic: "BIT 6, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::D)),
-        }),
-        0x73 => Some(Opcode {
-            mnemonic: "BIT 6, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::E)),
-        }),
-        0x74 => Some(Opcode {
-            mnemonic: "BIT 6, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::H)),
-        }),
-        0x75 => Some(Opcode {
-            mnemonic: "BIT 6, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::L)),
-        }),
-        0x76 => Some(Opcode {
-            mnemonic: "BIT 6, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Six)),
-        }),
-        0x77 => Some(Opcode {
-            mnemonic: "BIT 6, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Six, Register::A)),
-        }),
-        0x78 => Some(Opcode {
-            mnemonic: "BIT 7, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::B)),
-        }),
-        0x79 => Some(Opcode {
-            mnemonic: "BIT 7, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::C)),
-        }),
-        0x7A => Some(Opcode {
-            mnemonic: "BIT 7, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::D)),
-        }),
-        0x7B => Some(Opcode {
-            mnemonic: "BIT 7, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::E)),
-        }),
-        0x7C => Some(Opcode {
-            mnemonic: "BIT 7, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::H)),
-        }),
-        0x7D => Some(Opcode {
-            mnemonic: "BIT 7, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::L)),
-        }),
-        0x7E => Some(Opcode {
-            mnemonic: "BIT 7, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| bit_indirect_hl(cpu, memory, Bit::Seven)),
-        }),
-        0x7F => Some(Opcode {
-            mnemonic: "BIT 7, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| bit_r8(cpu, Bit::Seven, Register::A)),
-        }),
-        0x80 => Some(Opcode {
-            mnemonic: "RES 0, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::B)),
-        }),
-        0x81 => Some(Opcode {
-            mnemonic: "RES 0, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::C)),
-        }),
-        0x82 => Some(Opcode {
-            mnemonic: "RES 0, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::D)),
-        }),
-        0x83 => Some(Opcode {
-            mnemonic: "RES 0, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::E)),
-        }),
-        0x84 => Some(Opcode {
-            mnemonic: "RES 0, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::H)),
-        }),
-        0x85 => Some(Opcode {
-            mnemonic: "RES 0, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::L)),
-        }),
-        0x86 => Some(Opcode {
-            mnemonic: "RES 0, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Zero)),
-        }),
-        0x87 => Some(Opcode {
-            mnemonic: "RES 0, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Zero, Register::A)),
-        }),
-        0x88 => Some(Opcode {
-            mnemonic: "RES 1, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::B)),
-        }),
-        0x89 => Some(Opcode {
-            mnemonic: "RES 1, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::C)),
-        }),
-        0x8A => Some(Opcode {
-            mnemonic: "RES 1, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::D)),
-        }),
-        0x8B => Some(Opcode {
-            mnemonic: "RES 1, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::E)),
-        }),
-        0x8C => Some(Opcode {
-            mnemonic: "RES 1, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::H)),
-        }),
-        0x8D => Some(Opcode {
-            mnemonic: "RES 1, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::L)),
-        }),
-        0x8E => Some(Opcode {
-            mnemonic: "RES 1, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::One)),
-        }),
-        0x8F => Some(Opcode {
-            mnemonic: "RES 1, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::One, Register::A)),
-        }),
-        0x90 => Some(Opcode {
-            mnemonic: "RES 2, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::B)),
-        }),
-        0x91 => Some(Opcode {
-            mnemonic: "RES 2, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::C)),
-        }),
-        0x92 => Some(Opcode {
-            mnemonic: "RES 2, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::D)),
-        }),
-        0x93 => Some(Opcode {
-            mnemonic: "RES 2, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::E)),
-        }),
-        0x94 => Some(Opcode {
-            mnemonic: "RES 2, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::H)),
-        }),
-        0x95 => Some(Opcode {
-            mnemonic: "RES 2, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::L)),
-        }),
-        0x96 => Some(Opcode {
-            mnemonic: "RES 2, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Two)),
-        }),
-        0x97 => Some(Opcode {
-            mnemonic: "RES 2, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Two, Register::A)),
-        }),
-        0x98 => Some(Opcode {
-            mnemonic: "RES 3, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::B)),
-        }),
-        0x99 => Some(Opcode {
-            mnemonic: "RES 3, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::C)),
-        }),
-        0x9A => Some(Opcode {
-            mnemonic: "RES 3, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::D)),
-        }),
-        0x9B => Some(Opcode {
-            mnemonic: "RES 3, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::E)),
-        }),
-        0x9C => Some(Opcode {
-            mnemonic: "RES 3, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::H)),
-        }),
-        0x9D => Some(Opcode {
-            mnemonic: "RES 3, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::L)),
-        }),
-        0x9E => Some(Opcode {
-            mnemonic: "RES 3, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Three)),
-        }),
-        0x9F => Some(Opcode {
-            mnemonic: "RES 3, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Three, Register::A)),
-        }),
-        0xA0 => Some(Opcode {
-            mnemonic: "RES 4, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::B)),
-        }),
-        0xA1 => Some(Opcode {
-            mnemonic: "RES 4, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::C)),
-        }),
-        0xA2 => Some(Opcode {
-            mnemonic: "RES 4, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::D)),
-        }),
-        0xA3 => Some(Opcode {
-            mnemonic: "RES 4, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::E)),
-        }),
-        0xA4 => Some(Opcode {
-            mnemonic: "RES 4, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::H)),
-        }),
-        0xA5 => Some(Opcode {
-            mnemonic: "RES 4, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::L)),
-        }),
-        0xA6 => Some(Opcode {
-            mnemonic: "RES 4, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Four)),
-        }),
-        0xA7 => Some(Opcode {
-            mnemonic: "RES 4, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Four, Register::A)),
-        }),
-        0xA8 => Some(Opcode {
-            mnemonic: "RES 5, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::B)),
-        }),
-        0xA9 => Some(Opcode {
-            mnemonic: "RES 5, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::C)),
-        }),
-        0xAA => Some(Opcode {
-            mnemonic: "RES 5, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::D)),
-        }),
-        0xAB => Some(Opcode {
-            mnemonic: "RES 5, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::E)),
-        }),
-        0xAC => Some(Opcode {
-            mnemonic: "RES 5, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::H)),
-        }),
-        0xAD => Some(Opcode {
-            mnemonic: "RES 5, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::L)),
-        }),
-        0xAE => Some(Opcode {
-            mnemonic: "RES 5, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Five)),
-        }),
-        0xAF => Some(Opcode {
-            mnemonic: "RES 5, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Five, Register::A)),
-        }),
-        0xB0 => Some(Opcode {
-            mnemonic: "RES 6, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::B)),
-        }),
-        0xB1 => Some(Opcode {
-            mnemonic: "RES 6, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::C)),
-        }),
-        0xB2 => Some(Opcode {
-            mnemonic: "RES 6, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::D)),
-        }),
-        0xB3 => Some(Opcode {
-            mnemonic: "RES 6, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::E)),
-        }),
-        0xB4 => Some(Opcode {
-            mnemonic: "RES 6, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::H)),
-        }),
-        0xB5 => Some(Opcode {
-            mnemonic: "RES 6, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::L)),
-        }),
-        0xB6 => Some(Opcode {
-            mnemonic: "RES 6, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Six)),
-        }),
-        0xB7 => Some(Opcode {
-            mnemonic: "RES 6, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Six, Register::A)),
-        }),
-        0xB8 => Some(Opcode {
-            mnemonic: "RES 7, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::B)),
-        }),
-        0xB9 => Some(Opcode {
-            mnemonic: "RES 7, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::C)),
-        }),
-        0xBA => Some(Opcode {
-            mnemonic: "RES 7, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::D)),
-        }),
-        0xBB => Some(Opcode {
-            mnemonic: "RES 7, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::E)),
-        }),
-        0xBC => Some(Opcode {
-            mnemonic: "RES 7, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::H)),
-        }),
-        0xBD => Some(Opcode {
-            mnemonic: "RES 7, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::L)),
-        }),
-        0xBE => Some(Opcode {
-            mnemonic: "RES 7, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| res_indirect_hl(cpu, memory, Bit::Seven)),
-        }),
-        0xBF => Some(Opcode {
-            mnemonic: "RES 7, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| res_r8(cpu, Bit::Seven, Register::A)),
-        }),
-        0xC0 => Some(Opcode {
-            mnemonic: "SET 0, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::B)),
-        }),
-        0xC1 => Some(Opcode {
-            mnemonic: "SET 0, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::C)),
-        }),
-        0xC2 => Some(Opcode {
-            mnemonic: "SET 0, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::D)),
-        }),
-        0xC3 => Some(Opcode {
-            mnemonic: "SET 0, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::E)),
-        }),
-        0xC4 => Some(Opcode {
-            mnemonic: "SET 0, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::H)),
-        }),
-        0xC5 => Some(Opcode {
-            mnemonic: "SET 0, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::L)),
-        }),
-        0xC6 => Some(Opcode {
-            mnemonic: "SET 0, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Zero)),
-        }),
-        0xC7 => Some(Opcode {
-            mnemonic: "SET 0, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Zero, Register::A)),
-        }),
-        0xC8 => Some(Opcode {
-            mnemonic: "SET 1, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::B)),
-        }),
-        0xC9 => Some(Opcode {
-            mnemonic: "SET 1, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::C)),
-        }),
-        0xCA => Some(Opcode {
-            mnemonic: "SET 1, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::D)),
-        }),
-        0xCB => Some(Opcode {
-            mnemonic: "SET 1, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::E)),
-        }),
-        0xCC => Some(Opcode {
-            mnemonic: "SET 1, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::H)),
-        }),
-        0xCD => Some(Opcode {
-            mnemonic: "SET 1, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::L)),
-        }),
-        0xCE => Some(Opcode {
-            mnemonic: "SET 1, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::One)),
-        }),
-        0xCF => Some(Opcode {
-            mnemonic: "SET 1, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::One, Register::A)),
-        }),
-        0xD0 => Some(Opcode {
-            mnemonic: "SET 2, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::B)),
-        }),
-        0xD1 => Some(Opcode {
-            mnemonic: "SET 2, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::C)),
-        }),
-        0xD2 => Some(Opcode {
-            mnemonic: "SET 2, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::D)),
-        }),
-        0xD3 => Some(Opcode {
-            mnemonic: "SET 2, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::E)),
-        }),
-        0xD4 => Some(Opcode {
-            mnemonic: "SET 2, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::H)),
-        }),
-        0xD5 => Some(Opcode {
-            mnemonic: "SET 2, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::L)),
-        }),
-        0xD6 => Some(Opcode {
-            mnemonic: "SET 2, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Two)),
-        }),
-        0xD7 => Some(Opcode {
-            mnemonic: "SET 2, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Two, Register::A)),
-        }),
-        0xD8 => Some(Opcode {
-            mnemonic: "SET 3, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::B)),
-        }),
-        0xD9 => Some(Opcode {
-            mnemonic: "SET 3, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::C)),
-        }),
-        0xDA => Some(Opcode {
-            mnemonic: "SET 3, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::D)),
-        }),
-        0xDB => Some(Opcode {
-            mnemonic: "SET 3, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::E)),
-        }),
-        0xDC => Some(Opcode {
-            mnemonic: "SET 3, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::H)),
-        }),
-        0xDD => Some(Opcode {
-            mnemonic: "SET 3, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::L)),
-        }),
-        0xDE => Some(Opcode {
-            mnemonic: "SET 3, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Three)),
-        }),
-        0xDF => Some(Opcode {
-            mnemonic: "SET 3, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Three, Register::A)),
-        }),
-        0xE0 => Some(Opcode {
-            mnemonic: "SET 4, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::B)),
-        }),
-        0xE1 => Some(Opcode {
-            mnemonic: "SET 4, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::C)),
-        }),
-        0xE2 => Some(Opcode {
-            mnemonic: "SET 4, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::D)),
-        }),
-        0xE3 => Some(Opcode {
-            mnemonic: "SET 4, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::E)),
-        }),
-        0xE4 => Some(Opcode {
-            mnemonic: "SET 4, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::H)),
-        }),
-        0xE5 => Some(Opcode {
-            mnemonic: "SET 4, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::L)),
-        }),
-        0xE6 => Some(Opcode {
-            mnemonic: "SET 4, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Four)),
-        }),
-        0xE7 => Some(Opcode {
-            mnemonic: "SET 4, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Four, Register::A)),
-        }),
-        0xE8 => Some(Opcode {
-            mnemonic: "SET 5, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::B)),
-        }),
-        0xE9 => Some(Opcode {
-            mnemonic: "SET 5, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::C)),
-        }),
-        0xEA => Some(Opcode {
-            mnemonic: "SET 5, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::D)),
-        }),
-        0xEB => Some(Opcode {
-            mnemonic: "SET 5, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::E)),
-        }),
-        0xEC => Some(Opcode {
-            mnemonic: "SET 5, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::H)),
-        }),
-        0xED => Some(Opcode {
-            mnemonic: "SET 5, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::L)),
-        }),
-        0xEE => Some(Opcode {
-            mnemonic: "SET 5, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Five)),
-        }),
-        0xEF => Some(Opcode {
-            mnemonic: "SET 5, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Five, Register::A)),
-        }),
-        0xF0 => Some(Opcode {
-            mnemonic: "SET 6, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::B)),
-        }),
-        0xF1 => Some(Opcode {
-            mnemonic: "SET 6, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::C)),
-        }),
-        0xF2 => Some(Opcode {
-            mnemonic: "SET 6, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::D)),
-        }),
-        0xF3 => Some(Opcode {
-            mnemonic: "SET 6, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::E)),
-        }),
-        0xF4 => Some(Opcode {
-            mnemonic: "SET 6, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::H)),
-        }),
-        0xF5 => Some(Opcode {
-            mnemonic: "SET 6, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::L)),
-        }),
-        0xF6 => Some(Opcode {
-            mnemonic: "SET 6, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Six)),
-        }),
-        0xF7 => Some(Opcode {
-            mnemonic: "SET 6, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Six, Register::A)),
-        }),
-        0xF8 => Some(Opcode {
-            mnemonic: "SET 7, B".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::B)),
-        }),
-        0xF9 => Some(Opcode {
-            mnemonic: "SET 7, C".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::C)),
-        }),
-        0xFA => Some(Opcode {
-            mnemonic: "SET 7, D".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::D)),
-        }),
-        0xFB => Some(Opcode {
-            mnemonic: "SET 7, E".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::E)),
-        }),
-        0xFC => Some(Opcode {
-            mnemonic: "SET 7, H".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::H)),
-        }),
-        0xFD => Some(Opcode {
-            mnemonic: "SET 7, L".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::L)),
-        }),
-        0xFE => Some(Opcode {
-            mnemonic: "SET 7, [HL]".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, memory: &mut Memory| set_indirect_hl(cpu, memory, Bit::Seven)),
-        }),
-        0xFF => Some(Opcode {
-            mnemonic: "SET 7, A".to_string(),
-            size_bytes: 1,
-            handler: Some(|cpu: &mut Cpu, _| set_r8(cpu, Bit::Seven, Register::A)),
-        }),
-    }
-}
+    opcodes
+};
 
-fn add_hl_r16(cpu: &mut Cpu, r: RegisterWide) {
+fn add_hl_r16(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let hl = cpu.read_register_wide(RegisterWide::HL);
     let value = cpu.read_register_wide(r);
     let (result, overflowed) = hl.overflowing_add(value);
@@ -3260,6 +4753,7 @@ fn add_hl_r16(cpu: &mut Cpu, r: RegisterWide) {
         carry: Some(overflowed),
         ..Default::default()
     });
+    bus.tick();
 }
 
 fn add_r8(cpu: &mut Cpu, r: Register) {
@@ -3321,9 +4815,9 @@ enum Bit {
     Seven = 7,
 }
 
-fn bit_indirect_hl(cpu: &mut Cpu, memory: &mut Memory, b: Bit) {
+fn bit_indirect_hl(cpu: &mut Cpu, bus: &mut dyn Bus, b: Bit) {
     let hl = cpu.read_register_wide(RegisterWide::HL);
-    let value = memory.read(Address(hl));
+    let value = bus.read(Address(hl));
     let bit = value & (1 << b as u8);
     cpu.write_flags(WriteFlags {
         zero: Some(bit == 0),
@@ -3343,17 +4837,16 @@ fn bit_r8(cpu: &mut Cpu, b: Bit, r: Register) {
     })
 }
 
-fn call_a16(cpu: &mut Cpu, memory: &mut Memory) {
+fn call_a16(cpu: &mut Cpu, bus: &mut dyn Bus, taken: bool) {
     let pc = cpu.read_register_wide(RegisterWide::PC);
-    let msb = memory.read(Address(pc - 1));
-    let lsb = memory.read(Address(pc - 2));
+    let lsb = bus.read(Address(pc - 2));
+    let msb = bus.read(Address(pc - 1));
+    if !taken {
+        return;
+    }
+
     let address = u8_to_u16(msb, lsb);
-    let sp = cpu.read_register_wide(RegisterWide::SP);
-    let new_sp = sp - 2;
-    let (msb, lsb) = u16_to_u8(pc);
-    memory.write(Address(new_sp), lsb);
-    memory.write(Address(new_sp + 1), msb);
-    cpu.write_register_wide(RegisterWide::SP, new_sp);
+    push(cpu, bus, RegisterWide::PC);
     cpu.write_register_wide(RegisterWide::PC, address);
 }
 
@@ -3381,9 +4874,10 @@ fn dec_r8(cpu: &mut Cpu, r: Register) {
     cpu.write_register(r, result);
 }
 
-fn dec_r16(cpu: &mut Cpu, r: RegisterWide) {
+fn dec_r16(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let value = cpu.read_register_wide(r);
-    cpu.write_register_wide(r, value - 1);
+    cpu.write_register_wide(r, value.wrapping_sub(1));
+    bus.tick();
 }
 
 fn inc_r8(cpu: &mut Cpu, r: Register) {
@@ -3398,24 +4892,35 @@ fn inc_r8(cpu: &mut Cpu, r: Register) {
     });
 }
 
-fn inc_r16(cpu: &mut Cpu, r: RegisterWide) {
+fn inc_r16(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let value = cpu.read_register_wide(r);
-    cpu.write_register_wide(r, value + 1);
+    cpu.write_register_wide(r, value.wrapping_add(1));
+    bus.tick();
 }
 
-fn jump(cpu: &mut Cpu, memory: &mut Memory) {
+fn jump(cpu: &mut Cpu, bus: &mut dyn Bus, taken: bool) {
     let pc = cpu.read_register_wide(RegisterWide::PC);
-    let msb = memory.read(Address(pc - 1));
-    let lsb = memory.read(Address(pc - 2));
+    let lsb = bus.read(Address(pc - 2));
+    let msb = bus.read(Address(pc - 1));
+    if !taken {
+        return;
+    }
+
     let new_pc = util::u8_to_u16(msb, lsb);
     cpu.write_register_wide(RegisterWide::PC, new_pc);
+    bus.tick();
 }
 
-fn jump_relative(cpu: &mut Cpu, memory: &mut Memory) {
+fn jump_relative(cpu: &mut Cpu, bus: &mut dyn Bus, taken: bool) {
     let mut pc = cpu.read_register_wide(RegisterWide::PC);
-    let imm = memory.read(Address(pc - 1)) as i8;
+    let imm = bus.read(Address(pc - 1)) as i8;
+    if !taken {
+        return;
+    }
+
     pc = pc.wrapping_add_signed(imm.into());
     cpu.write_register_wide(RegisterWide::PC, pc);
+    bus.tick();
 }
 
 fn ld_r8_r8(cpu: &mut Cpu, dst_register: Register, src_register: Register) {
@@ -3433,13 +4938,13 @@ enum IndirectAddressingMode {
 
 fn ld_r8_indirect_r16(
     cpu: &mut Cpu,
-    memory: &mut Memory,
+    bus: &mut dyn Bus,
     dst_register: Register,
     src_register: RegisterWide,
     mode: IndirectAddressingMode,
 ) {
     let src_address = cpu.read_register_wide(src_register);
-    let value = memory.read(Address(src_address));
+    let value = bus.read(Address(src_address));
     cpu.write_register(dst_register, value);
     
     let register_value = cpu.read_register_wide(src_register);
@@ -3452,14 +4957,14 @@ fn ld_r8_indirect_r16(
 
 fn ld_indirect_r16_r8(
     cpu: &mut Cpu,
-    memory: &mut Memory,
+    bus: &mut dyn Bus,
     dst_register: RegisterWide,
     src_register: Register,
     mode: IndirectAddressingMode,
 ) {
     let dst_value = cpu.read_register_wide(dst_register);
     let src_value = cpu.read_register(src_register);
-    memory.write(Address(dst_value), src_value);
+    bus.write(Address(dst_value), src_value);
 
     let new_dst_register = match mode {
         IndirectAddressingMode::Retain => dst_value,
@@ -3469,16 +4974,16 @@ fn ld_indirect_r16_r8(
     cpu.write_register_wide(dst_register, new_dst_register);
 }
 
-fn ld_r8_n8(cpu: &mut Cpu, memory: &mut Memory, r: Register) {
+fn ld_r8_n8(cpu: &mut Cpu, bus: &mut dyn Bus, r: Register) {
     let pc = cpu.read_register_wide(RegisterWide::PC);
-    let imm = memory.read(Address(pc - 1));
+    let imm = bus.read(Address(pc - 1));
     cpu.write_register(r, imm);
 }
 
-fn ld_r16_n16(cpu: &mut Cpu, memory: &mut Memory, r: RegisterWide) {
+fn ld_r16_n16(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let pc = cpu.read_register_wide(RegisterWide::PC);
-    let msb = memory.read(Address(pc - 1));
-    let lsb = memory.read(Address(pc - 2));
+    let lsb = bus.read(Address(pc - 2));
+    let msb = bus.read(Address(pc - 1));
     let value = u8_to_u16(msb, lsb);
     cpu.write_register_wide(r, value);
 }
@@ -3496,30 +5001,32 @@ fn or_r8(cpu: &mut Cpu, r: Register) {
     });
 }
 
-fn pop(cpu: &mut Cpu, memory: &mut Memory, r: RegisterWide) {
+fn pop(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let sp = cpu.read_register_wide(RegisterWide::SP);
-    let lsb = memory.read(Address(sp));
-    let msb = memory.read(Address(sp + 1));
+    let lsb = bus.read(Address(sp));
+    let msb = bus.read(Address(sp + 1));
     cpu.write_register_wide(RegisterWide::SP, sp + 2);
     let value = u8_to_u16(msb, lsb);
     cpu.write_register_wide(r, value);
 }
 
-fn push(cpu: &mut Cpu, memory: &mut Memory, r: RegisterWide) {
+fn push(cpu: &mut Cpu, bus: &mut dyn Bus, r: RegisterWide) {
     let value = cpu.read_register_wide(r);
     let (msb, lsb) = util::u16_to_u8(value);
     let sp = cpu.read_register_wide(RegisterWide::SP);
-    memory.write(Address(sp - 1), msb);
-    memory.write(Address(sp - 2), lsb);
+    // SP is decremented before the first write
+    bus.tick();
+    bus.write(Address(sp - 1), msb);
+    bus.write(Address(sp - 2), lsb);
 
     cpu.write_register_wide(RegisterWide::SP, sp - 2);
 }
 
-fn res_indirect_hl(cpu: &mut Cpu, memory: &mut Memory, b: Bit) {
+fn res_indirect_hl(cpu: &mut Cpu, bus: &mut dyn Bus, b: Bit) {
     let hl = cpu.read_register_wide(RegisterWide::HL);
-    let value = memory.read(Address(hl));
+    let value = bus.read(Address(hl));
     let new_value = util::set_bits(value, 1, 1 << b as u8);
-    memory.write(Address(hl), new_value)
+    bus.write(Address(hl), new_value)
 }
 
 fn res_r8(cpu: &mut Cpu, b: Bit, r: Register) {
@@ -3586,14 +5093,21 @@ fn rrc_r8(cpu: &mut Cpu, r: Register) {
     cpu.write_register(r, result);
 }
 
-fn rst(cpu: &mut Cpu, memory: &mut Memory, address: u16) {
-    let pc = cpu.read_register_wide(RegisterWide::PC);
-    let (msb, lsb) = util::u16_to_u8(pc);
-    let sp = cpu.read_register_wide(RegisterWide::SP);
-    memory.write(Address(sp - 1), msb);
-    memory.write(Address(sp - 2), lsb);
+fn ret(cpu: &mut Cpu, bus: &mut dyn Bus) {
+    pop(cpu, bus, RegisterWide::PC);
+    bus.tick();
+}
 
-    cpu.write_register_wide(RegisterWide::SP, sp - 2);
+fn ret_conditional(cpu: &mut Cpu, bus: &mut dyn Bus, taken: bool) {
+    // Checking the condition takes a cycle of its own
+    bus.tick();
+    if taken {
+        ret(cpu, bus);
+    }
+}
+
+fn rst(cpu: &mut Cpu, bus: &mut dyn Bus, address: u16) {
+    push(cpu, bus, RegisterWide::PC);
     cpu.write_register_wide(RegisterWide::PC, address);
 }
 
@@ -3633,11 +5147,11 @@ fn sbc_r8(cpu: &mut Cpu, r: Register) {
     });
 }
 
-fn set_indirect_hl(cpu: &mut Cpu, memory: &mut Memory, bit: Bit) {
+fn set_indirect_hl(cpu: &mut Cpu, bus: &mut dyn Bus, bit: Bit) {
     let hl = cpu.read_register_wide(RegisterWide::HL); 
-    let value = memory.read(Address(hl));
+    let value = bus.read(Address(hl));
     let mask = 1 << bit as u8;
-    memory.write(Address(hl), util::set_bits(value, mask, mask));
+    bus.write(Address(hl), util::set_bits(value, mask, mask));
 }
 
 fn set_r8(cpu: &mut Cpu, bit: Bit, register: Register) {
@@ -3714,3 +5228,90 @@ fn xor_r8(cpu: &mut Cpu, register: Register) {
         carry: Some(false),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{Access, TestBus};
+    use crate::memory::Memory;
+
+    const ADDRESS_OPCODE: u16 = 0xC100;
+    const OPERAND_BYTE: u8 = 0x10;
+
+    /// Runs `opcode` from `ADDRESS_OPCODE` with flags `f`, with every operand byte set to
+    /// `OPERAND_BYTE` so taken jumps land somewhere else. Returns where PC was left and the bus.
+    fn run(opcode: &Opcode, f: u8) -> (u16, TestBus) {
+        let mut cpu = Cpu::init();
+        cpu.f = f;
+        cpu.sp = 0xD000;
+        cpu.h = 0xC0;
+        cpu.pc = ADDRESS_OPCODE + opcode.size_bytes as u16;
+        let mut memory = Memory::init();
+        memory.write(Address(0xFF50), 1);
+        for offset in 1..opcode.size_bytes as u16 {
+            memory.write(Address(ADDRESS_OPCODE + offset), OPERAND_BYTE);
+        }
+
+        let mut bus = TestBus::init(memory);
+        opcode.execute(&mut cpu, &mut bus);
+        (cpu.pc, bus)
+    }
+
+    fn implemented(table: &[Option<Opcode>; 256]) -> impl Iterator<Item = &Opcode> {
+        table.iter().flatten().filter(|opcode| opcode.handler.is_some())
+    }
+
+    #[test]
+    fn test_cycles() {
+        for (table, fetch_cycles) in [(&OPCODES, 1), (&PREFIXED_OPCODES, 2)] {
+            for opcode in implemented(table) {
+                let next = ADDRESS_OPCODE + opcode.size_bytes as u16;
+                let mut branches = Vec::new();
+                // Every condition is either met with all flags set or with none of them
+                for f in [0x00, 0xF0] {
+                    let (pc, bus) = run(opcode, f);
+                    let expected = match opcode.cycles {
+                        Cycles::Fixed(cycles) => cycles,
+                        Cycles::Branch { taken, not_taken } => {
+                            branches.push(pc != next);
+                            if pc != next { taken } else { not_taken }
+                        }
+                    };
+                    let cycles = fetch_cycles + bus.cycles;
+                    assert_eq!(expected as usize, cycles, "{} with F={:#04x}", opcode.mnemonic, f);
+                }
+
+                if matches!(opcode.cycles, Cycles::Branch { .. }) {
+                    let both_ways = branches.contains(&true) && branches.contains(&false);
+                    assert!(both_ways, "{} always went the same way", opcode.mnemonic);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_operand_read_first() {
+        // STOP skips over its operand without reading it
+        let opcodes = implemented(&OPCODES).chain(implemented(&PREFIXED_OPCODES));
+        for opcode in opcodes.filter(|opcode| opcode.mnemonic != "STOP n8") {
+            let (_, bus) = run(opcode, 0x00);
+            let operand_reads: Vec<Access> = (1..opcode.size_bytes as u16)
+                .map(|offset| Access::Read { address: ADDRESS_OPCODE + offset, value: OPERAND_BYTE })
+                .collect();
+            let first_accesses = bus.accesses.get(..operand_reads.len());
+            assert_eq!(Some(operand_reads.as_slice()), first_accesses, "{}", opcode.mnemonic);
+        }
+    }
+
+    #[test]
+    fn test_size_matches_operand() {
+        for opcode in OPCODES.iter().chain(&PREFIXED_OPCODES).flatten() {
+            let operand_bytes = match opcode.operand {
+                Operand::None => 0,
+                Operand::N8 | Operand::E8 | Operand::A8 => 1,
+                Operand::N16 | Operand::A16 => 2,
+            };
+            assert_eq!(1 + operand_bytes, opcode.size_bytes, "{}", opcode.mnemonic);
+        }
+    }
+}
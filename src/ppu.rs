@@ -1,15 +1,22 @@
+use crate::state::{StateError, StateReader, StateWriter};
 use crate::util::{bit, set_bits};
 use crate::{Address, Memory};
 use image::{GrayImage, Luma};
 use std::collections::VecDeque;
 
 const DOTS_PER_OAM_SCAN: usize = 80;
+// Mode 3 takes longer with SCX, the window or sprites, but never less than this
+const MIN_DOTS_PER_DRAWING: usize = 172;
 const DOTS_PER_SCANLINE: usize = 456;
 const SCANLINES_PER_FRAME: usize = 153;
 const SCANLINES_PER_VERTICAL_BLANK: usize = 10;
+// SCANLINES_PER_FRAME is the index of the last scanline, so a frame is one scanline longer
+pub const DOTS_PER_FRAME: usize = DOTS_PER_SCANLINE * (SCANLINES_PER_FRAME + 1);
 const PIXELS_PER_SCANLINE: u8 = 160;
 const TILE_DIMENSION: usize = 8;
 
+const ADDRESS_VRAM: u16 = 0x8000;
+const ADDRESS_OAM: u16 = 0xFE00;
 const ADDRESS_INTERRUPT_FLAG_REGISTER: u16 = 0xFF0F;
 const ADDRESS_LCDC_REGISTER: u16 = 0xFF40;
 const ADDRESS_LCD_STATUS_REGISTER: u16 = 0xFF41;
@@ -19,6 +26,7 @@ const ADDRESS_LY: u16 = 0xFF44;
 const ADDRESS_LYC: u16 = 0xFF45;
 const ADDRESS_BGP: u16 = 0xFF47;
 
+#[derive(Copy, Clone)]
 #[repr(u16)]
 enum BgWindowTileArea {
     Area8000 = 0x8000,
@@ -127,6 +135,33 @@ impl Sprite {
             && self.y_position <= ly + 16
             && self.y_position + height as u8 > ly + 16
     }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.u8(self.y_position);
+        writer.u8(self.x_position);
+        writer.u8(self.tile_number);
+        writer.bool(matches!(self.flags.priority, ObjectBackgroundPriority::Background));
+        writer.bool(self.flags.y_flip);
+        writer.bool(self.flags.x_flip);
+        writer.bool(self.flags.palette);
+    }
+
+    fn load_state(reader: &mut StateReader) -> Result<Self, StateError> {
+        Ok(Sprite {
+            y_position: reader.u8()?,
+            x_position: reader.u8()?,
+            tile_number: reader.u8()?,
+            flags: SpriteFlags {
+                priority: match reader.bool()? {
+                    false => ObjectBackgroundPriority::Object,
+                    true => ObjectBackgroundPriority::Background,
+                },
+                y_flip: reader.bool()?,
+                x_flip: reader.bool()?,
+                palette: reader.bool()?,
+            },
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -183,6 +218,53 @@ impl Pixel {
 
         sprite_pixel
     }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.u8(self.colour as u8);
+        writer.u8(match self.palette {
+            Palette::Bgp => 0,
+            Palette::Obp0 => 1,
+            Palette::Obp1 => 2,
+        });
+        writer.bool(matches!(self.priority, ObjectBackgroundPriority::Background));
+    }
+
+    fn load_state(reader: &mut StateReader) -> Result<Self, StateError> {
+        Ok(Pixel {
+            colour: load_pixel_colour(reader)?,
+            palette: match reader.u8()? {
+                0 => Palette::Bgp,
+                1 => Palette::Obp0,
+                2 => Palette::Obp1,
+                _ => return Err(StateError::Invalid("pixel palette")),
+            },
+            priority: match reader.bool()? {
+                false => ObjectBackgroundPriority::Object,
+                true => ObjectBackgroundPriority::Background,
+            },
+        })
+    }
+}
+
+fn load_pixel_colour(reader: &mut StateReader) -> Result<PixelColour, StateError> {
+    PixelColour::try_from(reader.u8()?).map_err(|_| StateError::Invalid("pixel colour"))
+}
+
+fn save_fifo(writer: &mut StateWriter, fifo: &VecDeque<Pixel>) {
+    writer.u8(fifo.len() as u8);
+    for pixel in fifo {
+        pixel.save_state(writer);
+    }
+}
+
+fn load_fifo(reader: &mut StateReader, fifo: &mut VecDeque<Pixel>) -> Result<(), StateError> {
+    // Keep the existing allocation, the sprite fetcher relies on the FIFO's capacity
+    fifo.clear();
+    for _ in 0..reader.u8()? {
+        fifo.push_back(Pixel::load_state(reader)?);
+    }
+
+    Ok(())
 }
 
 enum FetchStep {
@@ -193,6 +275,47 @@ enum FetchStep {
     Push([PixelColour; TILE_DIMENSION]),
 }
 
+impl FetchStep {
+    fn save_state(&self, writer: &mut StateWriter) {
+        match self {
+            FetchStep::Paused => writer.u8(0),
+            FetchStep::FetchTileNumber => writer.u8(1),
+            FetchStep::FetchTileLow(tile_number) => {
+                writer.u8(2);
+                writer.u8(*tile_number);
+            }
+            FetchStep::FetchTileHigh(tile_number, tile_data_low) => {
+                writer.u8(3);
+                writer.u8(*tile_number);
+                writer.u8(*tile_data_low);
+            }
+            FetchStep::Push(pixel_colours) => {
+                writer.u8(4);
+                for colour in pixel_colours {
+                    writer.u8(*colour as u8);
+                }
+            }
+        }
+    }
+
+    fn load_state(reader: &mut StateReader) -> Result<Self, StateError> {
+        Ok(match reader.u8()? {
+            0 => FetchStep::Paused,
+            1 => FetchStep::FetchTileNumber,
+            2 => FetchStep::FetchTileLow(reader.u8()?),
+            3 => FetchStep::FetchTileHigh(reader.u8()?, reader.u8()?),
+            4 => {
+                let mut pixel_colours = [PixelColour::White; TILE_DIMENSION];
+                for colour in pixel_colours.iter_mut() {
+                    *colour = load_pixel_colour(reader)?;
+                }
+                FetchStep::Push(pixel_colours)
+            }
+            _ => return Err(StateError::Invalid("fetch step")),
+        })
+    }
+}
+
 struct BackgroundFetcher {
     x_position: u8,
     fetch_step: FetchStep,
@@ -224,7 +347,7 @@ impl BackgroundFetcher {
                 // TODO: Are we fetching BG or window tile?
                 let tile_map_area = read_bg_tile_map_area(memory);
                 let y_offset = (32 * (((ly as u16 + scy) & 0xFF) / 8)) & 0x3FF;
-                let x_offset = (self.x_position as u16 + (scx & 0x1F)) & 0x3FF;
+                let x_offset = (self.x_position as u16 + scx / 8) & 0x1F;
                 let tile_number_address = tile_map_area as u16 + x_offset + y_offset;
                 let tile_number = memory.read(Address(tile_number_address));
                 self.fetch_step = FetchStep::FetchTileLow(tile_number);
@@ -275,9 +398,15 @@ impl SpriteFetcher {
         self.fifo.clear();
     }
 
+    // Row of the sprite's tile on scanline `ly`, counted from the sprite's top rather than the
+    // background's scroll
+    fn row(&self, ly: u8) -> u8 {
+        let sprite = self.sprite.expect("SpriteFetcher sprite is not None");
+        ly + 16 - sprite.y_position
+    }
+
     fn step(&mut self, memory: &Memory, ppu_x_position: u8) {
-        let ly = memory.read(Address(ADDRESS_LY)) as u16;
-        let scy = memory.read(Address(ADDRESS_SCY)) as u16;
+        let ly = memory.read(Address(ADDRESS_LY));
         let tile_data_area = BgWindowTileArea::Area8000;
 
         match &self.fetch_step {
@@ -287,12 +416,12 @@ impl SpriteFetcher {
                 self.fetch_step = FetchStep::FetchTileLow(sprite.tile_number);
             }
             FetchStep::FetchTileLow(tile_number) => {
-                let address = fetch_tile_data_address(tile_data_area, *tile_number, ly, scy);
+                let address = tile_row_address(tile_data_area, *tile_number, self.row(ly));
                 let tile_data_low = memory.read(Address(address));
                 self.fetch_step = FetchStep::FetchTileHigh(*tile_number, tile_data_low);
             }
             FetchStep::FetchTileHigh(tile_number, tile_data_low) => {
-                let address = fetch_tile_data_address(tile_data_area, *tile_number, ly, scy);
+                let address = tile_row_address(tile_data_area, *tile_number, self.row(ly));
                 let tile_data_high = memory.read(Address(address + 1));
 
                 let pixel_colours = line_bytes_to_pixel_colours(*tile_data_low, tile_data_high);
@@ -317,9 +446,52 @@ impl SpriteFetcher {
     }
 }
 
-pub struct Ppu {
-    // Dot count in the current scanline
-    dot: usize,
+/// How the pixels of each scanline are drawn. The mode, LY and interrupt timing is the same either
+/// way, apart from how long mode 3 (PpuMode::Drawing) lasts.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RendererKind {
+    /// Pushes a pixel per dot through the background and sprite FIFOs, so registers written part
+    /// way through a scanline take effect part way through it.
+    Fifo,
+    /// Draws a whole scanline at the start of mode 0 (PpuMode::HorizontalBlank) from VRAM and OAM as
+    /// they are then. Mode 3 always takes its shortest time. Faster, for when thousands of
+    /// frames matter more than mid-scanline effects.
+    Scanline,
+}
+
+impl RendererKind {
+    fn renderer(self) -> Box<dyn Renderer> {
+        match self {
+            RendererKind::Fifo => Box::new(FifoRenderer::init()),
+            RendererKind::Scanline => Box::new(ScanlineRenderer::init()),
+        }
+    }
+
+    pub fn save_state(self, writer: &mut StateWriter) {
+        writer.u8(self as u8);
+    }
+
+    pub fn load_state(reader: &mut StateReader) -> Result<Self, StateError> {
+        match reader.u8()? {
+            0 => Ok(RendererKind::Fifo),
+            1 => Ok(RendererKind::Scanline),
+            _ => Err(StateError::Invalid("renderer")),
+        }
+    }
+}
+
+/// Draws the pixels of each scanline for `Ppu`, which keeps track of modes, LY and interrupts.
+trait Renderer {
+    /// Called on every dot of mode 2 (PpuMode::OamScan). `dot` counts from the start of the scanline.
+    fn scan_oam(&mut self, memory: &Memory, ly: u8, dot: usize);
+    /// Called on every dot of mode 3 (PpuMode::Drawing). Returns true once the scanline is drawn,
+    /// which ends mode 3.
+    fn draw(&mut self, memory: &Memory, ly: u8, dot: usize, image_buffer: &mut GrayImage) -> bool;
+    fn save_state(&self, writer: &mut StateWriter);
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError>;
+}
+
+struct FifoRenderer {
     // Sprite buffer for current scanline
     sprite_buffer: Vec<Sprite>,
     // Pixels pushed in the current scanline
@@ -328,13 +500,11 @@ pub struct Ppu {
     sprite_fetcher: SpriteFetcher,
     // Number of pixels to discard from the background FIFO at the start of mode 3 (PpuMode::Drawing)
     discard_count: usize,
-    pub image_buffer: image::GrayImage,
 }
 
-impl Ppu {
-    pub fn init() -> Ppu {
-        Ppu {
-            dot: 0,
+impl FifoRenderer {
+    fn init() -> FifoRenderer {
+        FifoRenderer {
             sprite_buffer: Vec::<Sprite>::with_capacity(10),
             x_position: 0,
             background_fetcher: BackgroundFetcher {
@@ -348,10 +518,288 @@ impl Ppu {
                 sprite: None,
             },
             discard_count: 0,
+        }
+    }
+}
+
+impl Renderer for FifoRenderer {
+    fn scan_oam(&mut self, memory: &Memory, ly: u8, dot: usize) {
+        // Each sprite takes 2 dots to fetch, skip odd dots.
+        if dot % 2 == 0 {
+            let byte_offset = (dot / 2) * 4;
+            let sprite_address = Address(ADDRESS_OAM + byte_offset as u16);
+            let sprite_memory = memory.read_range(sprite_address, 4);
+            let sprite = Sprite::from(sprite_memory);
+            let sprite_height = SpriteHeight::Normal; // TODO: fetch from register
+
+            // Render conditions for sprite
+            if self.sprite_buffer.len() < 10 && sprite.visible(ly, sprite_height) {
+                self.sprite_buffer.push(sprite);
+            }
+        }
+
+        if dot == DOTS_PER_OAM_SCAN - 1 {
+            self.sprite_buffer
+                .sort_by(|s1, s2| (*s2).x_position.cmp(&s1.x_position));
+            // SCX mod 8 pixels should be discarded at the start of each scanline
+            let scx = memory.read(Address(ADDRESS_SCX)) as u16;
+            self.discard_count = (scx % 8) as usize;
+        }
+    }
+
+    fn draw(&mut self, memory: &Memory, ly: u8, _dot: usize, image_buffer: &mut GrayImage) -> bool {
+        // Initiate sprite fetch if the X-Position of any sprite in the sprite buffer
+        // is less than or equal to the current Pixel-X-Position + 8
+        if self.sprite_fetcher.paused()
+            && self
+                .sprite_buffer
+                .iter()
+                .any(|s| s.x_position <= self.x_position + 8)
+        {
+            self.background_fetcher.fetch_step = FetchStep::Paused;
+            self.sprite_fetcher.sprite = self.sprite_buffer.pop();
+            self.sprite_fetcher.fetch_step = FetchStep::FetchTileNumber;
+        };
+
+        self.background_fetcher.step(memory);
+        self.sprite_fetcher.step(memory, self.x_position);
+
+        if self.sprite_fetcher.paused() {
+            if self.background_fetcher.paused() {
+                self.background_fetcher.fetch_step = FetchStep::FetchTileNumber;
+            }
+
+            if let Some(background_pixel) = self.background_fetcher.fifo.pop_front() {
+                // Pause rendering while we discard SCX mod 8 pixels from leftmost tile
+                if self.discard_count > 0 {
+                    self.discard_count -= 1;
+
+                    return false;
+                }
+
+                let mixed_pixel = match self.sprite_fetcher.fifo.pop_front() {
+                    Some(sprite_pixel) => Pixel::mix(background_pixel, sprite_pixel),
+                    None => background_pixel,
+                };
+                image_buffer.put_pixel(
+                    self.x_position as u32,
+                    ly as u32,
+                    mixed_pixel.colour.to_grayscale(),
+                );
+
+                self.x_position += 1;
+                if self.x_position == PIXELS_PER_SCANLINE {
+                    self.x_position = 0;
+                    self.sprite_buffer.clear();
+                    self.background_fetcher.reset();
+                    self.background_fetcher.fifo.clear();
+                    self.sprite_fetcher.reset();
+
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.u8(self.sprite_buffer.len() as u8);
+        for sprite in &self.sprite_buffer {
+            sprite.save_state(writer);
+        }
+        writer.u8(self.x_position);
+
+        writer.u8(self.background_fetcher.x_position);
+        self.background_fetcher.fetch_step.save_state(writer);
+        save_fifo(writer, &self.background_fetcher.fifo);
+
+        self.sprite_fetcher.fetch_step.save_state(writer);
+        save_fifo(writer, &self.sprite_fetcher.fifo);
+        writer.bool(self.sprite_fetcher.sprite.is_some());
+        if let Some(sprite) = &self.sprite_fetcher.sprite {
+            sprite.save_state(writer);
+        }
+
+        writer.u32(self.discard_count as u32);
+    }
+
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.sprite_buffer.clear();
+        for _ in 0..reader.u8()? {
+            self.sprite_buffer.push(Sprite::load_state(reader)?);
+        }
+        self.x_position = reader.u8()?;
+
+        self.background_fetcher.x_position = reader.u8()?;
+        self.background_fetcher.fetch_step = FetchStep::load_state(reader)?;
+        load_fifo(reader, &mut self.background_fetcher.fifo)?;
+
+        self.sprite_fetcher.fetch_step = FetchStep::load_state(reader)?;
+        load_fifo(reader, &mut self.sprite_fetcher.fifo)?;
+        self.sprite_fetcher.sprite = match reader.bool()? {
+            true => Some(Sprite::load_state(reader)?),
+            false => None,
+        };
+
+        self.discard_count = reader.u32()? as usize;
+
+        Ok(())
+    }
+}
+
+/// Draws the same background and sprites as `FifoRenderer`, but a scanline at a time.
+struct ScanlineRenderer {
+    // Sprites on the scanline being drawn, kept to avoid allocating for every scanline
+    sprite_buffer: Vec<Sprite>,
+}
+
+impl ScanlineRenderer {
+    fn init() -> ScanlineRenderer {
+        ScanlineRenderer {
+            sprite_buffer: Vec::<Sprite>::with_capacity(10),
+        }
+    }
+
+    fn draw_scanline(&mut self, memory: &Memory, ly: u8, image_buffer: &mut GrayImage) {
+        let vram = memory.vram();
+        let oam = memory.oam();
+        let scy = memory.read(Address(ADDRESS_SCY));
+        let scx = memory.read(Address(ADDRESS_SCX));
+        let tile_map_area = read_bg_tile_map_area(memory) as u16;
+        let tile_data_area = read_bg_window_tile_area(memory);
+
+        // The first 10 sprites in OAM on this scanline. Where they overlap, the one furthest left
+        // wins, then the one first in OAM.
+        self.sprite_buffer.clear();
+        self.sprite_buffer.extend(
+            oam.chunks(4)
+                .map(Sprite::from)
+                .filter(|sprite| sprite.visible(ly, SpriteHeight::Normal))
+                .take(10),
+        );
+        self.sprite_buffer.sort_by_key(|sprite| sprite.x_position);
+
+        let y = ly.wrapping_add(scy);
+        for screen_x in 0..PIXELS_PER_SCANLINE {
+            let x = screen_x.wrapping_add(scx);
+            let tile_number_address = tile_map_area + 32 * (y / 8) as u16 + (x / 8) as u16;
+            let tile_number = vram[(tile_number_address - ADDRESS_VRAM) as usize];
+            let row_address = tile_row_address(tile_data_area, tile_number, y % 8) - ADDRESS_VRAM;
+            let row_address = row_address as usize;
+            let background_pixel = Pixel {
+                colour: pixel_colour(vram[row_address], vram[row_address + 1], (x % 8) as usize),
+                palette: Palette::Bgp,
+                priority: ObjectBackgroundPriority::Background, // Irrelevant for background pixels
+            };
+
+            let sprite_pixel = self
+                .sprite_buffer
+                .iter()
+                .find_map(|sprite| sprite_pixel(vram, sprite, ly, screen_x));
+            let mixed_pixel = match sprite_pixel {
+                Some(sprite_pixel) => Pixel::mix(background_pixel, sprite_pixel),
+                None => background_pixel,
+            };
+            image_buffer.put_pixel(screen_x as u32, ly as u32, mixed_pixel.colour.to_grayscale());
+        }
+    }
+}
+
+impl Renderer for ScanlineRenderer {
+    fn scan_oam(&mut self, _memory: &Memory, _ly: u8, _dot: usize) {}
+
+    fn draw(&mut self, memory: &Memory, ly: u8, dot: usize, image_buffer: &mut GrayImage) -> bool {
+        if dot < DOTS_PER_OAM_SCAN + MIN_DOTS_PER_DRAWING - 1 {
+            return false;
+        }
+
+        self.draw_scanline(memory, ly, image_buffer);
+        true
+    }
+
+    // Nothing is left in flight between dots
+    fn save_state(&self, _writer: &mut StateWriter) {}
+
+    fn load_state(&mut self, _reader: &mut StateReader) -> Result<(), StateError> {
+        Ok(())
+    }
+}
+
+/// Address of `row` of a background or window tile. In the 0x8800 area tiles are numbered from
+/// 0x9000, with numbers 128 to 255 counting back from -128.
+fn tile_row_address(tile_data_area: BgWindowTileArea, tile_number: u8, row: u8) -> u16 {
+    let tile_address = match tile_data_area {
+        BgWindowTileArea::Area8000 => 0x8000 + tile_number as u16 * 16,
+        BgWindowTileArea::Area8800 => 0x9000_u16.wrapping_add((tile_number as i8 as i16 * 16) as u16),
+    };
+
+    tile_address + 2 * row as u16
+}
+
+/// The pixel `sprite` draws at `x` on scanline `ly`, if it covers it and isn't transparent there.
+fn sprite_pixel(vram: &[u8], sprite: &Sprite, ly: u8, x: u8) -> Option<Pixel> {
+    // Sprite positions are offset by 8 pixels to the right and 16 down from the screen
+    let column = (x + 8).checked_sub(sprite.x_position)? as usize;
+    if column >= TILE_DIMENSION {
+        return None;
+    }
+
+    let row = (ly + 16 - sprite.y_position) as usize;
+    let row_address = sprite.tile_number as usize * 16 + 2 * row;
+    let colour = pixel_colour(vram[row_address], vram[row_address + 1], column);
+    if colour == PixelColour::White {
+        return None;
+    }
+
+    Some(Pixel {
+        colour,
+        palette: match sprite.flags.palette {
+            false => Palette::Obp0,
+            true => Palette::Obp1,
+        },
+        priority: sprite.flags.priority,
+    })
+}
+
+pub struct Ppu {
+    // Dot count in the current scanline
+    dot: usize,
+    renderer_kind: RendererKind,
+    renderer: Box<dyn Renderer>,
+    // Frames completed since power on, counted on entering VBlank
+    pub frame_count: usize,
+    pub image_buffer: image::GrayImage,
+}
+
+impl Ppu {
+    pub fn init() -> Ppu {
+        Ppu::init_with_renderer(RendererKind::Fifo)
+    }
+
+    pub fn init_with_renderer(renderer: RendererKind) -> Ppu {
+        Ppu {
+            dot: 0,
+            renderer_kind: renderer,
+            renderer: renderer.renderer(),
+            frame_count: 0,
             image_buffer: GrayImage::new(160, 144),
         }
     }
 
+    pub fn renderer(&self) -> RendererKind {
+        self.renderer_kind
+    }
+
+    /// Switches renderer. Anything the old one had in flight is dropped, so the scanline being
+    /// drawn may come out wrong.
+    pub fn set_renderer(&mut self, renderer: RendererKind) {
+        if renderer != self.renderer_kind {
+            self.renderer_kind = renderer;
+            self.renderer = renderer.renderer();
+        }
+    }
+
     pub fn get_tile_buffer(&self, memory: &Memory) -> image::GrayImage {
         let tile_count = 384usize;
         let width_in_tiles = 12usize;
@@ -381,6 +829,41 @@ impl Ppu {
         tile_buffer
     }
 
+    /// The renderer's kind and whatever it has in flight are saved separately, with
+    /// `RendererKind::save_state` and `save_renderer_state`, so this layout is the same for every
+    /// renderer.
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.u32(self.dot as u32);
+        writer.u64(self.frame_count as u64);
+        writer.bytes(self.image_buffer.as_raw());
+    }
+
+    /// `version` is the save state's. Version 1 kept the renderer's state in the middle of this
+    /// chunk.
+    pub fn load_state(&mut self, reader: &mut StateReader, version: u16) -> Result<(), StateError> {
+        self.dot = reader.u32()? as usize;
+        if version == 1 {
+            self.renderer.load_state(reader)?;
+        }
+        self.frame_count = reader.u64()? as usize;
+        let (width, height) = self.image_buffer.dimensions();
+        let mut image_buffer = vec![0; (width * height) as usize];
+        reader.bytes_into(&mut image_buffer, "image buffer")?;
+        self.image_buffer = GrayImage::from_raw(width, height, image_buffer)
+            .expect("Image buffer size was checked");
+
+        Ok(())
+    }
+
+    /// Saves what the renderer has in flight, which only a `Ppu` using the same renderer can load.
+    pub fn save_renderer_state(&self, writer: &mut StateWriter) {
+        self.renderer.save_state(writer);
+    }
+
+    pub fn load_renderer_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.renderer.load_state(reader)
+    }
+
     pub fn step(&mut self, memory: &mut Memory) {
         let ppu_mode = read_ppu_mode(memory);
         let ly = memory.read(Address(ADDRESS_LY));
@@ -389,81 +872,16 @@ impl Ppu {
 
         match ppu_mode {
             PpuMode::OamScan => {
-                // Each sprite takes 2 dots to fetch, skip odd dots.
-                if self.dot % 2 == 0 {
-                    let byte_offset = (self.dot / 2) * 4;
-                    let sprite_address = Address(0xFE00 + byte_offset as u16);
-                    let sprite_memory = memory.read_range(sprite_address, 4);
-                    let sprite = Sprite::from(sprite_memory);
-                    let sprite_height = SpriteHeight::Normal; // TODO: fetch from register
-                    
-                    // Render conditions for sprite
-                    if self.sprite_buffer.len() < 10 && sprite.visible(ly, sprite_height) {
-                        self.sprite_buffer.push(sprite);
-                    }
-                }
+                self.renderer.scan_oam(memory, ly, self.dot);
 
                 self.dot += 1;
                 if self.dot == DOTS_PER_OAM_SCAN {
                     write_ppu_mode(memory, PpuMode::Drawing);
-                    self.sprite_buffer
-                        .sort_by(|s1, s2| (*s2).x_position.cmp(&s1.x_position));
-                    // SCX mod 8 pixels should be discarded at the start of each scanline
-                    let scx = memory.read(Address(ADDRESS_SCX)) as u16;
-                    self.discard_count = (scx % 8) as usize;
                 }
             }
             PpuMode::Drawing => {
-                // Initiate sprite fetch if the X-Position of any sprite in the sprite buffer
-                // is less than or equal to the current Pixel-X-Position + 8
-                if self.sprite_fetcher.paused()
-                    && self
-                        .sprite_buffer
-                        .iter()
-                        .any(|s| s.x_position <= self.x_position + 8)
-                {
-                    self.background_fetcher.fetch_step = FetchStep::Paused;
-                    self.sprite_fetcher.sprite = self.sprite_buffer.pop();
-                    self.sprite_fetcher.fetch_step = FetchStep::FetchTileNumber;
-                };
-
-                self.background_fetcher.step(memory);
-                self.sprite_fetcher.step(memory, self.x_position);
-
-                if self.sprite_fetcher.paused() {
-                    if self.background_fetcher.paused() {
-                        self.background_fetcher.fetch_step = FetchStep::FetchTileNumber;
-                    }
-
-                    if let Some(background_pixel) = self.background_fetcher.fifo.pop_front() {
-                        // Pause rendering while we discard SCX mod 8 pixels from leftmost tile
-                        if self.discard_count > 0 {
-                            self.discard_count -= 1;
-                            self.dot += 1;
-
-                            return;
-                        }
-
-                        let mixed_pixel = match self.sprite_fetcher.fifo.pop_front() {
-                            Some(sprite_pixel) => Pixel::mix(background_pixel, sprite_pixel),
-                            None => background_pixel,
-                        };
-                        self.image_buffer.put_pixel(
-                            self.x_position as u32,
-                            ly as u32,
-                            mixed_pixel.colour.to_grayscale(),
-                        );
-
-                        self.x_position += 1;
-                        if self.x_position == PIXELS_PER_SCANLINE {
-                            write_ppu_mode(memory, PpuMode::HorizontalBlank);
-                            self.x_position = 0;
-                            self.sprite_buffer.clear();
-                            self.background_fetcher.reset();
-                            self.background_fetcher.fifo.clear();
-                            self.sprite_fetcher.reset();
-                        }
-                    }
+                if self.renderer.draw(memory, ly, self.dot, &mut self.image_buffer) {
+                    write_ppu_mode(memory, PpuMode::HorizontalBlank);
                 }
                 self.dot += 1;
             }
@@ -474,6 +892,7 @@ impl Ppu {
                     memory.write(Address(ADDRESS_LY), ly + 1);
                     let ppu_mode =
                         if ly as usize >= SCANLINES_PER_FRAME - SCANLINES_PER_VERTICAL_BLANK {
+                            self.frame_count += 1;
                             PpuMode::VerticalBlank
                         } else {
                             PpuMode::OamScan
@@ -505,7 +924,7 @@ impl Ppu {
 
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-enum PixelColour {
+pub enum PixelColour {
     White,
     LightGray,
     DarkGray,
@@ -527,7 +946,7 @@ impl TryFrom<u8> for PixelColour {
 }
 
 impl PixelColour {
-    fn to_grayscale(&self) -> Luma<u8> {
+    pub fn to_grayscale(&self) -> Luma<u8> {
         match self {
             Self::White => Luma([255]),
             Self::LightGray => Luma([211]),
@@ -535,22 +954,36 @@ impl PixelColour {
             Self::Black => Luma([0]),
         }
     }
+
+    pub fn from_grayscale(luma: Luma<u8>) -> Self {
+        match luma {
+            Luma([255]) => Self::White,
+            Luma([211]) => Self::LightGray,
+            Luma([169]) => Self::DarkGray,
+            _ => Self::Black,
+        }
+    }
 }
 
-fn line_bytes_to_pixel_colours(first_byte: u8, second_byte: u8) -> [PixelColour; TILE_DIMENSION] {
+pub fn line_bytes_to_pixel_colours(first_byte: u8, second_byte: u8) -> [PixelColour; TILE_DIMENSION] {
     let mut pixels = [PixelColour::White; TILE_DIMENSION];
     for i in 0..TILE_DIMENSION {
-        let bit = 7 - i;
-        let lsb = first_byte >> bit & 1;
-        let msb = second_byte >> bit & 1;
-
-        pixels[i] = PixelColour::try_from((msb << 1) | lsb)
-            .expect("Only 2 bits should be passed to PixelColour::try_from");
+        pixels[i] = pixel_colour(first_byte, second_byte, i);
     }
 
     pixels
 }
 
+/// The colour of pixel `column`, counting from the left, of a tile row stored as two bytes.
+fn pixel_colour(first_byte: u8, second_byte: u8, column: usize) -> PixelColour {
+    let bit = 7 - column;
+    let lsb = first_byte >> bit & 1;
+    let msb = second_byte >> bit & 1;
+
+    PixelColour::try_from((msb << 1) | lsb)
+        .expect("Only 2 bits should be passed to PixelColour::try_from")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
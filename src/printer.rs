@@ -0,0 +1,367 @@
+use crate::ppu::{line_bytes_to_pixel_colours, PixelColour};
+use crate::serial::{LinkEvent, SerialLink};
+use crate::util::u8_to_u16;
+
+use image::GrayImage;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+const MAGIC: [u8; 2] = [0x88, 0x33];
+const DEVICE_ID: u8 = 0x81;
+
+const COMMAND_INIT: u8 = 0x01;
+const COMMAND_PRINT: u8 = 0x02;
+const COMMAND_DATA: u8 = 0x04;
+const COMMAND_STATUS: u8 = 0x0F;
+
+const STATUS_CHECKSUM_ERROR: u8 = 0b0000_0001;
+const STATUS_BUSY: u8 = 0b0000_0010;
+const STATUS_UNPROCESSED_DATA: u8 = 0b0000_1000;
+
+const TILES_PER_ROW: usize = 20;
+const BYTES_PER_TILE: usize = 16;
+const TILE_DIMENSION: usize = 8;
+// Printer RAM holds 9 bands of 2 tile rows
+const IMAGE_DATA_CAPACITY: usize = 9 * 2 * TILES_PER_ROW * BYTES_PER_TILE;
+// Number of status queries the printer reports busy for after printing
+const PRINT_BUSY_POLLS: u8 = 4;
+
+enum PacketStep {
+    Magic(usize),
+    Command,
+    Compression,
+    LengthLow,
+    LengthHigh(u8),
+    Data,
+    ChecksumLow,
+    ChecksumHigh(u8),
+    KeepAlive,
+    Status,
+}
+
+struct Packet {
+    command: u8,
+    compressed: bool,
+    length: u16,
+    data: Vec<u8>,
+    checksum: u16,
+}
+
+/// A Game Boy Printer attached to the serial port.
+///
+/// Printed images are written as PNGs to `output_dir`.
+pub struct Printer {
+    output_dir: PathBuf,
+    step: PacketStep,
+    packet: Packet,
+    image_data: Vec<u8>,
+    status: u8,
+    busy_polls: u8,
+    print_count: usize,
+    reply: Option<u8>,
+    events: VecDeque<LinkEvent>,
+}
+
+impl Printer {
+    pub fn init(output_dir: PathBuf) -> Self {
+        Self {
+            output_dir,
+            step: PacketStep::Magic(0),
+            packet: Packet {
+                command: 0,
+                compressed: false,
+                length: 0,
+                data: Vec::new(),
+                checksum: 0,
+            },
+            image_data: Vec::with_capacity(IMAGE_DATA_CAPACITY),
+            status: 0,
+            busy_polls: 0,
+            print_count: 0,
+            reply: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Advances the packet state machine by one byte and returns the printer's reply.
+    fn receive_byte(&mut self, byte: u8) -> u8 {
+        let mut reply = 0x00;
+
+        self.step = match self.step {
+            PacketStep::Magic(i) if byte == MAGIC[i] => match i + 1 == MAGIC.len() {
+                true => PacketStep::Command,
+                false => PacketStep::Magic(i + 1),
+            },
+            PacketStep::Magic(_) => PacketStep::Magic(0),
+            PacketStep::Command => {
+                self.packet.command = byte;
+                self.packet.checksum = byte as u16;
+                PacketStep::Compression
+            }
+            PacketStep::Compression => {
+                self.packet.compressed = byte & 1 == 1;
+                self.packet.checksum = self.packet.checksum.wrapping_add(byte as u16);
+                PacketStep::LengthLow
+            }
+            PacketStep::LengthLow => {
+                self.packet.checksum = self.packet.checksum.wrapping_add(byte as u16);
+                PacketStep::LengthHigh(byte)
+            }
+            PacketStep::LengthHigh(lsb) => {
+                self.packet.checksum = self.packet.checksum.wrapping_add(byte as u16);
+                self.packet.length = u8_to_u16(byte, lsb);
+                self.packet.data.clear();
+                match self.packet.length {
+                    0 => PacketStep::ChecksumLow,
+                    _ => PacketStep::Data,
+                }
+            }
+            PacketStep::Data => {
+                self.packet.checksum = self.packet.checksum.wrapping_add(byte as u16);
+                self.packet.data.push(byte);
+                match self.packet.data.len() == self.packet.length as usize {
+                    true => PacketStep::ChecksumLow,
+                    false => PacketStep::Data,
+                }
+            }
+            PacketStep::ChecksumLow => PacketStep::ChecksumHigh(byte),
+            PacketStep::ChecksumHigh(lsb) => {
+                match u8_to_u16(byte, lsb) == self.packet.checksum {
+                    true => self.execute_packet(),
+                    false => self.status |= STATUS_CHECKSUM_ERROR,
+                }
+                PacketStep::KeepAlive
+            }
+            PacketStep::KeepAlive => {
+                reply = DEVICE_ID;
+                PacketStep::Status
+            }
+            PacketStep::Status => {
+                reply = self.status;
+                if self.busy_polls > 0 {
+                    self.busy_polls -= 1;
+                    if self.busy_polls == 0 {
+                        self.status &= !STATUS_BUSY;
+                    }
+                }
+                PacketStep::Magic(0)
+            }
+        };
+
+        reply
+    }
+
+    fn execute_packet(&mut self) {
+        self.status &= !STATUS_CHECKSUM_ERROR;
+
+        match self.packet.command {
+            COMMAND_INIT => {
+                self.image_data.clear();
+                self.status = 0;
+                self.busy_polls = 0;
+            }
+            COMMAND_DATA => {
+                let data = match self.packet.compressed {
+                    true => decompress(&self.packet.data),
+                    false => self.packet.data.clone(),
+                };
+                let remaining = IMAGE_DATA_CAPACITY - self.image_data.len();
+                self.image_data.extend(data.into_iter().take(remaining));
+                if !self.image_data.is_empty() {
+                    self.status |= STATUS_UNPROCESSED_DATA;
+                }
+            }
+            COMMAND_PRINT => {
+                let sheets = self.packet.data.first().copied().unwrap_or(0);
+                let palette = self.packet.data.get(2).copied().unwrap_or(0);
+
+                // Zero sheets only feeds paper
+                if sheets > 0 && !self.image_data.is_empty() {
+                    self.save_image(palette);
+                }
+                self.image_data.clear();
+                self.status = (self.status & !STATUS_UNPROCESSED_DATA) | STATUS_BUSY;
+                self.busy_polls = PRINT_BUSY_POLLS;
+            }
+            COMMAND_STATUS => {}
+            command => self.events.push_back(LinkEvent::UnknownPrinterCommand(command)),
+        }
+    }
+
+    fn save_image(&mut self, palette: u8) {
+        let image = render(&self.image_data, palette);
+        // Skip prints left over from earlier sessions rather than overwriting them
+        let mut path = self.output_dir.join(format!("print-{}.png", self.print_count));
+        while path.exists() {
+            self.print_count += 1;
+            path = self.output_dir.join(format!("print-{}.png", self.print_count));
+        }
+        self.print_count += 1;
+
+        let event = match image.save(&path) {
+            Ok(_) => LinkEvent::Printed(path),
+            Err(e) => LinkEvent::PrintFailed(path, e),
+        };
+        self.events.push_back(event);
+    }
+}
+
+impl SerialLink for Printer {
+    fn send(&mut self, byte: u8) {
+        self.reply = Some(self.receive_byte(byte));
+    }
+
+    fn receive(&mut self) -> Option<u8> {
+        self.reply.take()
+    }
+
+    fn take_event(&mut self) -> Option<LinkEvent> {
+        self.events.pop_front()
+    }
+}
+
+/// Expands run-length encoded printer data.
+///
+/// Each run starts with a control byte. With bit 7 set, the following byte is repeated
+/// `(control & 0x7F) + 2` times; otherwise `control + 1` literal bytes follow.
+fn decompress(data: &[u8]) -> Vec<u8> {
+    let mut decompressed = Vec::with_capacity(IMAGE_DATA_CAPACITY);
+    let mut bytes = data.iter();
+
+    while let Some(&control) = bytes.next() {
+        if control & 0x80 != 0 {
+            let count = (control & 0x7F) as usize + 2;
+            if let Some(&byte) = bytes.next() {
+                decompressed.extend(std::iter::repeat_n(byte, count));
+            }
+        } else {
+            let count = control as usize + 1;
+            decompressed.extend(bytes.by_ref().take(count));
+        }
+    }
+
+    decompressed
+}
+
+fn render(image_data: &[u8], palette: u8) -> GrayImage {
+    // A palette of 0 is treated as the default mapping by real printers
+    let palette = match palette {
+        0 => 0b1110_0100,
+        _ => palette,
+    };
+
+    let tile_rows = image_data.len().div_ceil(TILES_PER_ROW * BYTES_PER_TILE);
+    let width = TILES_PER_ROW * TILE_DIMENSION;
+    let height = tile_rows * TILE_DIMENSION;
+    let mut image = GrayImage::new(width as u32, height as u32);
+
+    for (tile_idx, tile_chunk) in image_data.chunks(BYTES_PER_TILE).enumerate() {
+        let offset_x = (tile_idx % TILES_PER_ROW) * TILE_DIMENSION;
+        let offset_y = (tile_idx / TILES_PER_ROW) * TILE_DIMENSION;
+
+        for (row_idx, line_bytes) in tile_chunk.chunks_exact(2).enumerate() {
+            let pixel_colours = line_bytes_to_pixel_colours(line_bytes[0], line_bytes[1]);
+            for (column_idx, colour) in pixel_colours.iter().enumerate() {
+                let shade = (palette >> (*colour as u8 * 2)) & 0b11;
+                let shade = PixelColour::try_from(shade).expect("Palette shades are 2 bits");
+                image.put_pixel(
+                    (offset_x + column_idx) as u32,
+                    (offset_y + row_idx) as u32,
+                    shade.to_grayscale(),
+                );
+            }
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_packet(printer: &mut Printer, command: u8, data: &[u8]) -> (u8, u8) {
+        let (length_msb, length_lsb) = crate::util::u16_to_u8(data.len() as u16);
+        let mut body = vec![command, 0x00, length_lsb, length_msb];
+        body.extend_from_slice(data);
+        let checksum = body.iter().fold(0u16, |sum, &b| sum.wrapping_add(b as u16));
+        let (checksum_msb, checksum_lsb) = crate::util::u16_to_u8(checksum);
+
+        for byte in MAGIC.iter().chain(body.iter()).chain([checksum_lsb, checksum_msb].iter()) {
+            assert_eq!(0x00, printer.receive_byte(*byte));
+        }
+
+        (printer.receive_byte(0x00), printer.receive_byte(0x00))
+    }
+
+    #[test]
+    fn test_decompress() {
+        assert_eq!(vec![0xAA; 5], decompress(&[0x83, 0xAA]));
+        assert_eq!(vec![0x01, 0x02, 0x03], decompress(&[0x02, 0x01, 0x02, 0x03]));
+        assert_eq!(
+            vec![0x01, 0xFF, 0xFF],
+            decompress(&[0x00, 0x01, 0x80, 0xFF])
+        );
+    }
+
+    /// Returns an empty directory of its own for `test` to print to.
+    fn output_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("jameboy-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn print_blank_image(printer: &mut Printer) {
+        send_packet(printer, COMMAND_INIT, &[]);
+        send_packet(printer, COMMAND_DATA, &[0x00; 640]);
+        send_packet(printer, COMMAND_DATA, &[]);
+        send_packet(printer, COMMAND_PRINT, &[0x01, 0x13, 0xE4, 0x40]);
+    }
+
+    #[test]
+    fn test_print_packets() {
+        let output_dir = output_dir("print-packets");
+        let mut printer = Printer::init(output_dir.clone());
+
+        assert_eq!((DEVICE_ID, 0x00), send_packet(&mut printer, COMMAND_INIT, &[]));
+        assert_eq!(
+            (DEVICE_ID, STATUS_UNPROCESSED_DATA),
+            send_packet(&mut printer, COMMAND_DATA, &[0xFF; 640])
+        );
+        assert_eq!(
+            (DEVICE_ID, STATUS_UNPROCESSED_DATA),
+            send_packet(&mut printer, COMMAND_DATA, &[])
+        );
+        assert_eq!(
+            (DEVICE_ID, STATUS_BUSY),
+            send_packet(&mut printer, COMMAND_PRINT, &[0x01, 0x13, 0xE4, 0x40])
+        );
+
+        let image = image::open(output_dir.join("print-0.png")).unwrap().to_luma8();
+        assert_eq!((160, 16), image.dimensions());
+        assert!(image.pixels().all(|p| p.0 == [0]));
+        let printed = output_dir.join("print-0.png");
+        assert!(matches!(printer.take_event(), Some(LinkEvent::Printed(path)) if path == printed));
+
+        send_packet(&mut printer, 0x7F, &[]);
+        assert!(matches!(printer.take_event(), Some(LinkEvent::UnknownPrinterCommand(0x7F))));
+        assert!(printer.take_event().is_none());
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn test_print_keeps_earlier_prints() {
+        let output_dir = output_dir("keeps-earlier-prints");
+        std::fs::write(output_dir.join("print-0.png"), b"earlier").unwrap();
+
+        let mut printer = Printer::init(output_dir.clone());
+        print_blank_image(&mut printer);
+        print_blank_image(&mut printer);
+
+        assert_eq!(b"earlier", std::fs::read(output_dir.join("print-0.png")).unwrap().as_slice());
+        assert!(output_dir.join("print-1.png").exists());
+        assert!(output_dir.join("print-2.png").exists());
+        std::fs::remove_dir_all(output_dir).unwrap();
+    }
+}
@@ -0,0 +1,219 @@
+use crate::state::StateError;
+use crate::Jameboy;
+use std::collections::VecDeque;
+
+/// Ring buffer of periodic save states that gameplay can be rewound through.
+///
+/// Only the newest snapshot is kept whole. Every older one is stored as a delta against the
+/// snapshot taken after it, which is mostly a handful of changed bytes in RAM and the PPU.
+pub struct Rewind {
+    capacity: usize,
+    interval: usize,
+    latest: Option<Vec<u8>>,
+    latest_frame: usize,
+    deltas: VecDeque<(usize, Vec<u8>)>,
+}
+
+impl Rewind {
+    /// Keeps up to `capacity` snapshots, one taken every `interval` frames.
+    pub fn init(capacity: usize, interval: usize) -> Self {
+        assert!(capacity > 0 && interval > 0);
+
+        Self {
+            capacity,
+            interval,
+            latest: None,
+            latest_frame: 0,
+            deltas: VecDeque::new(),
+        }
+    }
+
+    /// Takes a snapshot if one is due for the current frame. Call once per frame.
+    pub fn record(&mut self, jameboy: &Jameboy) {
+        let frame = jameboy.ppu.frame_count;
+        if !frame.is_multiple_of(self.interval) {
+            return;
+        }
+        if self.latest.is_some() && frame == self.latest_frame {
+            return;
+        }
+
+        let state = jameboy.save_state();
+        if let Some(previous) = self.latest.replace(state) {
+            let delta = encode_delta(self.latest.as_ref().unwrap(), &previous);
+            self.deltas.push_back((self.latest_frame, delta));
+            if self.deltas.len() >= self.capacity {
+                self.deltas.pop_front();
+            }
+        }
+        self.latest_frame = frame;
+    }
+
+    /// Restores the newest snapshot, or the one before it if the machine is already there.
+    /// Returns false once there is nothing older left to go back to. If the snapshot can't be
+    /// loaded, the history is cleared and the error returned.
+    pub fn rewind(&mut self, jameboy: &mut Jameboy) -> Result<bool, StateError> {
+        let Some(mut state) = self.latest.take() else {
+            return Ok(false);
+        };
+
+        if jameboy.save_state() == state {
+            match self.deltas.pop_back() {
+                Some((frame, delta)) => {
+                    state = apply_delta(&state, &delta);
+                    self.latest_frame = frame;
+                }
+                None => {
+                    self.latest = Some(state);
+                    return Ok(false);
+                }
+            }
+        }
+
+        if let Err(e) = jameboy.load_state(&state) {
+            self.clear();
+            return Err(e);
+        }
+        // The restored snapshot stays the newest, so recording picks up again from here
+        self.latest = Some(state);
+
+        Ok(true)
+    }
+
+    pub fn len(&self) -> usize {
+        self.deltas.len() + self.latest.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.latest = None;
+        self.deltas.clear();
+    }
+}
+
+/// Encodes `target` as the XOR against `base`, as runs of unchanged bytes followed by runs of
+/// changed ones: `[target length: u32]` then repeated `[skip: u32][length: u32][length bytes]`.
+fn encode_delta(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let byte_at = |data: &[u8], i: usize| data.get(i).copied().unwrap_or(0);
+    let mut delta = Vec::new();
+    delta.extend_from_slice(&(target.len() as u32).to_le_bytes());
+
+    let mut i = 0;
+    while i < target.len() {
+        let start = i;
+        while i < target.len() && byte_at(base, i) == target[i] {
+            i += 1;
+        }
+        let skip = i - start;
+
+        let start = i;
+        while i < target.len() && byte_at(base, i) != target[i] {
+            i += 1;
+        }
+
+        delta.extend_from_slice(&(skip as u32).to_le_bytes());
+        delta.extend_from_slice(&((i - start) as u32).to_le_bytes());
+        delta.extend((start..i).map(|j| byte_at(base, j) ^ target[j]));
+    }
+
+    delta
+}
+
+fn apply_delta(base: &[u8], delta: &[u8]) -> Vec<u8> {
+    let read_u32 = |position: usize| {
+        u32::from_le_bytes(delta[position..position + 4].try_into().unwrap()) as usize
+    };
+
+    let length = read_u32(0);
+    let mut target = base.to_vec();
+    target.resize(length, 0);
+
+    let mut position = 4;
+    let mut i = 0;
+    while position < delta.len() {
+        i += read_u32(position);
+        let changed = read_u32(position + 4);
+        position += 8;
+
+        for (byte, xor) in target[i..i + changed].iter_mut().zip(&delta[position..]) {
+            *byte ^= xor;
+        }
+        i += changed;
+        position += changed;
+    }
+
+    target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boot::Model;
+
+    #[test]
+    fn test_delta_round_trip() {
+        let base = [1, 2, 3, 4, 5, 6, 7, 8];
+        for target in [
+            vec![1, 2, 3, 4, 5, 6, 7, 8],
+            vec![1, 9, 3, 4, 5, 0, 0, 8],
+            vec![9, 2, 3],
+            vec![1, 2, 3, 4, 5, 6, 7, 8, 0, 10, 11],
+            vec![],
+        ] {
+            assert_eq!(target, apply_delta(&base, &encode_delta(&base, &target)));
+        }
+
+        // Unchanged runs cost nothing beyond the header
+        assert_eq!(12, encode_delta(&base, &base).len());
+    }
+
+    #[test]
+    fn test_rewind() {
+        let mut jameboy = Jameboy::init();
+        let mut rom = vec![0; 0x8000];
+        // loop: INC A; LD [HL], A; INC L; JR loop
+        rom[0x100..0x105].copy_from_slice(&[0x3C, 0x77, 0x2C, 0x18, 0xFB]);
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+
+        let mut rewind = Rewind::init(3, 2);
+        let mut states = Vec::new();
+        for _ in 0..8 {
+            jameboy.run_frame();
+            rewind.record(&jameboy);
+            if jameboy.ppu.frame_count.is_multiple_of(2) {
+                states.push(jameboy.save_state());
+            }
+        }
+        assert_eq!(3, rewind.len());
+
+        // The machine is already at frame 8, so rewinding goes to 6 then 4. Older ones were dropped.
+        for expected in states.iter().rev().skip(1).take(2) {
+            assert_eq!(Ok(true), rewind.rewind(&mut jameboy));
+            assert_eq!(*expected, jameboy.save_state());
+        }
+        assert_eq!(Ok(false), rewind.rewind(&mut jameboy));
+        assert_eq!(4, jameboy.ppu.frame_count);
+
+        // Playing on from a rewound state records from there
+        jameboy.run_frame();
+        jameboy.run_frame();
+        rewind.record(&jameboy);
+        assert_eq!(2, rewind.len());
+        assert_eq!(Ok(true), rewind.rewind(&mut jameboy));
+        assert_eq!(states[1], jameboy.save_state());
+    }
+
+    #[test]
+    fn test_rewind_reports_bad_snapshot() {
+        let mut jameboy = Jameboy::init();
+        let mut rewind = Rewind::init(3, 1);
+        rewind.latest = Some(b"not a state".to_vec());
+
+        assert_eq!(Err(StateError::BadMagic), rewind.rewind(&mut jameboy));
+        assert!(rewind.is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+use crate::memory::{Address, Memory};
+use crate::state::{StateError, StateReader, StateWriter};
+use crate::util::{bit, set_bits};
+
+use std::io;
+use std::path::PathBuf;
+
+const ADDRESS_SB: u16 = 0xFF01;
+const ADDRESS_SC: u16 = 0xFF02;
+const ADDRESS_INTERRUPT_FLAG_REGISTER: u16 = 0xFF0F;
+
+// The internal clock shifts one bit every 128 M-cycles (8192 Hz)
+const CYCLES_PER_TRANSFER: usize = 8 * 128;
+// Receiving can cost a syscall, so a port waiting on its peer only checks once per bit time of the
+// internal clock
+const CYCLES_PER_POLL: usize = 128;
+
+/// Something that happened on the link that the frontend should tell the user about.
+#[derive(Debug)]
+pub enum LinkEvent {
+    /// The peer went away, because of the error if there was one.
+    Disconnected(Option<io::Error>),
+    /// The printer wrote a print to this file.
+    Printed(PathBuf),
+    /// The printer couldn't write a print to this file.
+    PrintFailed(PathBuf, image::ImageError),
+    /// The printer was sent a command it doesn't know, which it ignored.
+    UnknownPrinterCommand(u8),
+}
+
+/// The other end of a link cable.
+///
+/// Bytes are exchanged whole rather than bit by bit. The clock master sends its byte once it has
+/// been shifted out and completes the transfer when the peer replies with its own, so a peer that
+/// is running behind stalls the master instead of missing the transfer.
+pub trait SerialLink {
+    /// Sends a byte to the peer. Must not block.
+    fn send(&mut self, byte: u8);
+
+    /// Returns the next byte received from the peer, if any. Must not block.
+    fn receive(&mut self) -> Option<u8>;
+
+    /// Whether the peer is still there. Once it's gone, transfers finish as if nothing were plugged
+    /// in rather than waiting for a reply that won't come.
+    fn connected(&self) -> bool {
+        true
+    }
+
+    /// Returns the oldest event the frontend hasn't been given yet.
+    fn take_event(&mut self) -> Option<LinkEvent> {
+        None
+    }
+}
+
+pub(crate) enum TransferState {
+    Idle,
+    Shifting { cycles: usize },
+    AwaitingReply,
+}
+
+pub struct Serial {
+    link: Option<Box<dyn SerialLink>>,
+    transfer_state: TransferState,
+    // M-cycles until a waiting port next checks for a byte. Not saved, like the link itself.
+    poll_countdown: usize,
+}
+
+fn request_serial_interrupt(memory: &mut Memory) {
+    let interrupt_flag_register = memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER));
+    memory.write(
+        Address(ADDRESS_INTERRUPT_FLAG_REGISTER),
+        set_bits(interrupt_flag_register, 0b0000_1000, 0b0000_1000),
+    );
+}
+
+fn complete_transfer(memory: &mut Memory, received: u8) {
+    let sc = memory.read(Address(ADDRESS_SC));
+    memory.write(Address(ADDRESS_SB), received);
+    memory.write(Address(ADDRESS_SC), set_bits(sc, 0, 0b1000_0000));
+    request_serial_interrupt(memory);
+}
+
+impl Serial {
+    pub fn init() -> Self {
+        Self {
+            link: None,
+            transfer_state: TransferState::Idle,
+            poll_countdown: 0,
+        }
+    }
+
+    pub fn connect(&mut self, link: Box<dyn SerialLink>) {
+        self.link = Some(link);
+    }
+
+    /// Returns the oldest event from the link, see `SerialLink::take_event`.
+    pub fn take_link_event(&mut self) -> Option<LinkEvent> {
+        self.link.as_mut()?.take_event()
+    }
+
+    fn poll_due(&mut self) -> bool {
+        if self.poll_countdown == 0 {
+            self.poll_countdown = CYCLES_PER_POLL - 1;
+            true
+        } else {
+            self.poll_countdown -= 1;
+            false
+        }
+    }
+
+    /// Saves the transfer in progress. The link itself is not part of the state.
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        match self.transfer_state {
+            TransferState::Idle => writer.u8(0),
+            TransferState::Shifting { cycles } => {
+                writer.u8(1);
+                writer.u32(cycles as u32);
+            }
+            TransferState::AwaitingReply => writer.u8(2),
+        }
+    }
+
+    /// Reads the transfer state written by `save_state`, to be applied with `restore_transfer`.
+    pub(crate) fn load_state(reader: &mut StateReader) -> Result<TransferState, StateError> {
+        match reader.u8()? {
+            0 => Ok(TransferState::Idle),
+            1 => Ok(TransferState::Shifting { cycles: reader.u32()? as usize }),
+            2 => Ok(TransferState::AwaitingReply),
+            _ => Err(StateError::Invalid("serial transfer state")),
+        }
+    }
+
+    pub(crate) fn restore_transfer(&mut self, transfer_state: TransferState) {
+        // Without a link there is no reply to wait for, so let the transfer finish unconnected
+        self.transfer_state = match (transfer_state, &self.link) {
+            (TransferState::AwaitingReply, None) => TransferState::Shifting { cycles: CYCLES_PER_TRANSFER - 1 },
+            (transfer_state, _) => transfer_state,
+        };
+    }
+
+    /// Advances the serial port by one M-cycle.
+    pub fn step(&mut self, memory: &mut Memory) {
+        let sc = memory.read(Address(ADDRESS_SC));
+        let transfer_requested = bit(sc, 7) != 0;
+        let internal_clock = bit(sc, 0) != 0;
+
+        if !transfer_requested {
+            self.transfer_state = TransferState::Idle;
+            return;
+        }
+
+        match self.transfer_state {
+            TransferState::Idle if internal_clock => {
+                self.transfer_state = TransferState::Shifting { cycles: 1 };
+            }
+            TransferState::Idle => {
+                // Externally clocked: wait for the peer to clock a byte in
+                if !self.poll_due() {
+                    return;
+                }
+                if let Some(link) = self.link.as_mut() {
+                    if let Some(received) = link.receive() {
+                        link.send(memory.read(Address(ADDRESS_SB)));
+                        complete_transfer(memory, received);
+                    }
+                }
+            }
+            TransferState::Shifting { cycles } => {
+                if cycles + 1 < CYCLES_PER_TRANSFER {
+                    self.transfer_state = TransferState::Shifting { cycles: cycles + 1 };
+                    return;
+                }
+
+                match self.link.as_mut() {
+                    Some(link) if link.connected() => {
+                        link.send(memory.read(Address(ADDRESS_SB)));
+                        self.transfer_state = TransferState::AwaitingReply;
+                        self.poll_countdown = 0;
+                    }
+                    _ => {
+                        // With nothing connected the data line is pulled high
+                        complete_transfer(memory, 0xFF);
+                        self.transfer_state = TransferState::Idle;
+                    }
+                }
+            }
+            TransferState::AwaitingReply => {
+                if !self.poll_due() {
+                    return;
+                }
+                let link = self.link.as_mut().expect("Serial link is connected while awaiting a reply");
+                if let Some(received) = link.receive() {
+                    complete_transfer(memory, received);
+                    self.transfer_state = TransferState::Idle;
+                } else if !link.connected() {
+                    // The peer went away mid-transfer, so finish as if the cable had been pulled out
+                    complete_transfer(memory, 0xFF);
+                    self.transfer_state = TransferState::Idle;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    struct EchoLink {
+        pending: VecDeque<u8>,
+    }
+
+    impl SerialLink for EchoLink {
+        fn send(&mut self, byte: u8) {
+            self.pending.push_back(byte.wrapping_add(1));
+        }
+
+        fn receive(&mut self) -> Option<u8> {
+            self.pending.pop_front()
+        }
+    }
+
+    /// A peer that never sends anything, counting how often it's asked.
+    struct SilentLink {
+        receives: Rc<Cell<usize>>,
+    }
+
+    impl SerialLink for SilentLink {
+        fn send(&mut self, _byte: u8) {}
+
+        fn receive(&mut self) -> Option<u8> {
+            self.receives.set(self.receives.get() + 1);
+            None
+        }
+    }
+
+    /// A peer that disconnects as soon as it's sent a byte.
+    struct DisconnectingLink {
+        connected: bool,
+    }
+
+    impl SerialLink for DisconnectingLink {
+        fn send(&mut self, _byte: u8) {
+            self.connected = false;
+        }
+
+        fn receive(&mut self) -> Option<u8> {
+            None
+        }
+
+        fn connected(&self) -> bool {
+            self.connected
+        }
+    }
+
+    #[test]
+    fn test_transfer_without_link() {
+        let mut memory = Memory::init();
+        let mut serial = Serial::init();
+        memory.write(Address(ADDRESS_SB), 0x42);
+        memory.write(Address(ADDRESS_SC), 0b1000_0001);
+
+        for _ in 0..CYCLES_PER_TRANSFER {
+            serial.step(&mut memory);
+        }
+
+        assert_eq!(0xFF, memory.read(Address(ADDRESS_SB)));
+        assert_eq!(0b0000_0001, memory.read(Address(ADDRESS_SC)));
+        assert_eq!(0b1110_1000, memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER)));
+    }
+
+    #[test]
+    fn test_transfer_waits_for_reply() {
+        let mut memory = Memory::init();
+        let mut serial = Serial::init();
+        serial.connect(Box::new(EchoLink { pending: VecDeque::new() }));
+        memory.write(Address(ADDRESS_SB), 0x42);
+        memory.write(Address(ADDRESS_SC), 0b1000_0001);
+
+        for _ in 0..CYCLES_PER_TRANSFER {
+            serial.step(&mut memory);
+        }
+        assert_eq!(0b1000_0001, memory.read(Address(ADDRESS_SC)));
+
+        serial.step(&mut memory);
+        assert_eq!(0x43, memory.read(Address(ADDRESS_SB)));
+        assert_eq!(0b0000_0001, memory.read(Address(ADDRESS_SC)));
+    }
+
+    #[test]
+    fn test_transfer_ends_when_peer_disconnects() {
+        let mut memory = Memory::init();
+        let mut serial = Serial::init();
+        serial.connect(Box::new(DisconnectingLink { connected: true }));
+        memory.write(Address(ADDRESS_SB), 0x42);
+        memory.write(Address(ADDRESS_SC), 0b1000_0001);
+
+        for _ in 0..=CYCLES_PER_TRANSFER {
+            serial.step(&mut memory);
+        }
+
+        assert_eq!(0xFF, memory.read(Address(ADDRESS_SB)));
+        assert_eq!(0b0000_0001, memory.read(Address(ADDRESS_SC)));
+        assert_eq!(0b1110_1000, memory.read(Address(ADDRESS_INTERRUPT_FLAG_REGISTER)));
+    }
+
+    #[test]
+    fn test_waiting_polls_link_once_per_bit() {
+        let mut memory = Memory::init();
+        let mut serial = Serial::init();
+        let receives = Rc::new(Cell::new(0));
+        serial.connect(Box::new(SilentLink { receives: receives.clone() }));
+        memory.write(Address(ADDRESS_SC), 0b1000_0000);
+
+        for _ in 0..10 * CYCLES_PER_POLL {
+            serial.step(&mut memory);
+        }
+
+        assert_eq!(10, receives.get());
+    }
+}
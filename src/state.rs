@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt;
+
+const MAGIC: [u8; 4] = *b"JMBS";
+
+/// Save state format version.
+///
+/// Only bumped for changes older versions can't read. New components get their own chunk and new
+/// fields are appended to the end of an existing chunk, and both are ignored by older readers.
+pub const VERSION: u16 = 2;
+
+pub type ChunkTag = [u8; 4];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum StateError {
+    BadMagic,
+    UnsupportedVersion(u16),
+    Truncated,
+    MissingChunk(ChunkTag),
+    Invalid(&'static str),
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a file written by jameboy"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "file version {} is newer than this build supports", version)
+            }
+            Self::Truncated => write!(f, "file is truncated"),
+            Self::MissingChunk(tag) => {
+                write!(f, "file has no {} chunk", String::from_utf8_lossy(tag))
+            }
+            Self::Invalid(what) => write!(f, "file has an invalid {}", what),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+/// Writes a save state: a header followed by tagged, length-prefixed chunks.
+pub struct StateWriter {
+    buffer: Vec<u8>,
+}
+
+impl StateWriter {
+    pub fn init() -> Self {
+        Self::with_header(MAGIC, VERSION)
+    }
+
+    /// Starts a file in the same chunked format but with its own magic and version.
+    pub(crate) fn with_header(magic: [u8; 4], version: u16) -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&magic);
+        buffer.extend_from_slice(&version.to_le_bytes());
+
+        Self { buffer }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Writes a chunk whose contents are written by `write`.
+    pub fn chunk(&mut self, tag: ChunkTag, write: impl FnOnce(&mut StateWriter)) {
+        self.buffer.extend_from_slice(&tag);
+        let length_position = self.buffer.len();
+        self.buffer.extend_from_slice(&[0; 4]);
+
+        write(self);
+
+        let length = (self.buffer.len() - length_position - 4) as u32;
+        self.buffer[length_position..length_position + 4].copy_from_slice(&length.to_le_bytes());
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, value: &[u8]) {
+        self.u32(value.len() as u32);
+        self.buffer.extend_from_slice(value);
+    }
+}
+
+/// Reads a save state written by `StateWriter`.
+pub struct StateReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> StateReader<'a> {
+    fn init(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Checks the header and splits the state into its chunks.
+    pub fn chunks(data: &'a [u8]) -> Result<Chunks<'a>, StateError> {
+        Self::chunks_with_header(data, MAGIC, VERSION)
+    }
+
+    /// Like `chunks`, for files written with `StateWriter::with_header`.
+    pub(crate) fn chunks_with_header(
+        data: &'a [u8],
+        magic: [u8; 4],
+        version: u16,
+    ) -> Result<Chunks<'a>, StateError> {
+        let mut reader = Self::init(data);
+        if reader.take(magic.len()).map_err(|_| StateError::BadMagic)? != magic {
+            return Err(StateError::BadMagic);
+        }
+
+        let file_version = reader.u16()?;
+        if file_version > version {
+            return Err(StateError::UnsupportedVersion(file_version));
+        }
+
+        let mut chunks = HashMap::new();
+        while reader.position < data.len() {
+            let tag: ChunkTag = reader.take(4)?.try_into().expect("Chunk tags are 4 bytes");
+            let length = reader.u32()? as usize;
+            chunks.insert(tag, reader.take(length)?);
+        }
+
+        Ok(Chunks { version: file_version, chunks })
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], StateError> {
+        let end = self.position.checked_add(count).ok_or(StateError::Truncated)?;
+        let slice = self.data.get(self.position..end).ok_or(StateError::Truncated)?;
+        self.position = end;
+
+        Ok(slice)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, StateError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, StateError> {
+        Ok(self.u8()? != 0)
+    }
+
+    pub fn u16(&mut self) -> Result<u16, StateError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, StateError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, StateError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], StateError> {
+        let length = self.u32()? as usize;
+        self.take(length)
+    }
+
+    /// Reads bytes written by `StateWriter::bytes` into a fixed size buffer.
+    pub fn bytes_into(&mut self, buffer: &mut [u8], what: &'static str) -> Result<(), StateError> {
+        let bytes = self.bytes()?;
+        if bytes.len() != buffer.len() {
+            return Err(StateError::Invalid(what));
+        }
+        buffer.copy_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+pub struct Chunks<'a> {
+    version: u16,
+    chunks: HashMap<ChunkTag, &'a [u8]>,
+}
+
+impl<'a> Chunks<'a> {
+    /// The version the file was written with, for reading chunks laid out differently before.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn get(&self, tag: ChunkTag) -> Result<StateReader<'a>, StateError> {
+        self.chunks
+            .get(&tag)
+            .map(|data| StateReader::init(data))
+            .ok_or(StateError::MissingChunk(tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_round_trip() {
+        let mut writer = StateWriter::init();
+        writer.chunk(*b"ONE ", |w| {
+            w.u8(0x12);
+            w.u16(0x3456);
+            w.bytes(&[1, 2, 3]);
+        });
+        // Chunks unknown to the reader are skipped
+        writer.chunk(*b"NEW ", |w| w.u64(u64::MAX));
+        writer.chunk(*b"TWO ", |w| w.bool(true));
+        let data = writer.finish();
+
+        let chunks = StateReader::chunks(&data).unwrap();
+        let mut one = chunks.get(*b"ONE ").unwrap();
+        assert_eq!(Ok(0x12), one.u8());
+        assert_eq!(Ok(0x3456), one.u16());
+        assert_eq!(Ok(&[1, 2, 3][..]), one.bytes());
+        assert_eq!(Err(StateError::Truncated), one.u8());
+        assert_eq!(Ok(true), chunks.get(*b"TWO ").unwrap().bool());
+        assert_eq!(
+            Err(StateError::MissingChunk(*b"CPU ")),
+            chunks.get(*b"CPU ").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_headers() {
+        assert_eq!(Some(StateError::BadMagic), StateReader::chunks(b"JM").err());
+        assert_eq!(Some(StateError::BadMagic), StateReader::chunks(b"PNG\0\x01\x00").err());
+
+        let mut newer = MAGIC.to_vec();
+        newer.extend_from_slice(&(VERSION + 1).to_le_bytes());
+        assert_eq!(
+            Some(StateError::UnsupportedVersion(VERSION + 1)),
+            StateReader::chunks(&newer).err()
+        );
+    }
+}
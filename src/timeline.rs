@@ -0,0 +1,206 @@
+use crate::joypad::Buttons;
+use crate::Jameboy;
+use std::collections::VecDeque;
+
+/// Execution history for stepping backwards through a program.
+///
+/// Save states are taken every `interval` steps and whenever the input changes. Going back restores
+/// the closest checkpoint before the target and re-executes up to it, which is exact as long as the
+/// machine is deterministic. A serial link to the outside world isn't, so replays past transfers
+/// won't see the same bytes again.
+pub struct Timeline {
+    capacity: usize,
+    interval: u64,
+    step: u64,
+    checkpoints: VecDeque<(u64, Vec<u8>)>,
+}
+
+impl Timeline {
+    /// Keeps up to `capacity` checkpoints, one taken every `interval` steps.
+    pub fn init(capacity: usize, interval: u64) -> Self {
+        assert!(capacity > 0 && interval > 0);
+
+        Self {
+            capacity,
+            interval,
+            step: 0,
+            checkpoints: VecDeque::new(),
+        }
+    }
+
+    /// Number of steps executed since the timeline started.
+    pub fn position(&self) -> u64 {
+        self.step
+    }
+
+    /// The earliest step that can still be returned to.
+    pub fn start(&self) -> Option<u64> {
+        self.checkpoints.front().map(|(step, _)| *step)
+    }
+
+    /// Forgets the history, for when the machine state was replaced from outside the timeline.
+    pub fn clear(&mut self) {
+        self.step = 0;
+        self.checkpoints.clear();
+    }
+
+    /// Executes a single instruction, taking a checkpoint first if one is due.
+    pub fn step(&mut self, jameboy: &mut Jameboy) {
+        if self.step.is_multiple_of(self.interval) || self.checkpoints.is_empty() {
+            self.checkpoint(jameboy);
+        }
+
+        jameboy.step();
+        self.step += 1;
+    }
+
+    /// Updates the input. Replays have to see it change at the same step, so this takes a checkpoint.
+    pub fn set_input(&mut self, jameboy: &mut Jameboy, buttons: Buttons) {
+        if jameboy.input() != buttons {
+            jameboy.set_input(buttons);
+            self.checkpoint(jameboy);
+        }
+    }
+
+    /// Goes back a single instruction. Returns false at the start of the history.
+    pub fn step_back(&mut self, jameboy: &mut Jameboy) -> bool {
+        match self.step.checked_sub(1) {
+            Some(target) => self.seek(jameboy, target),
+            None => false,
+        }
+    }
+
+    /// Goes back to the last time an instruction at `breakpoints` was about to execute.
+    /// Returns false and stays put if that never happened within the history.
+    pub fn reverse_continue(&mut self, jameboy: &mut Jameboy, breakpoints: impl Fn(u16) -> bool) -> bool {
+        let current = self.step;
+
+        // Search one checkpoint interval at a time, newest first, replaying each to find the latest hit
+        let mut end = current;
+        for index in (0..self.checkpoints.len()).rev() {
+            let (start, state) = &self.checkpoints[index];
+            if *start >= end {
+                continue;
+            }
+
+            load(jameboy, state);
+            let mut hit = None;
+            for step in *start..end {
+                if breakpoints(jameboy.cpu.pc) {
+                    hit = Some(step);
+                }
+                jameboy.step();
+            }
+
+            if let Some(hit) = hit {
+                return self.seek(jameboy, hit);
+            }
+            end = *start;
+        }
+
+        self.seek(jameboy, current);
+        false
+    }
+
+    /// Puts the machine back to how it was at `target`, dropping the history after it.
+    fn seek(&mut self, jameboy: &mut Jameboy, target: u64) -> bool {
+        let Some(index) = self.checkpoints.iter().rposition(|(step, _)| *step <= target) else {
+            return false;
+        };
+
+        self.checkpoints.truncate(index + 1);
+        let (start, state) = &self.checkpoints[index];
+        load(jameboy, state);
+        for _ in *start..target {
+            jameboy.step();
+        }
+        self.step = target;
+
+        true
+    }
+
+    fn checkpoint(&mut self, jameboy: &Jameboy) {
+        if let Some((step, _)) = self.checkpoints.back() {
+            if *step == self.step {
+                self.checkpoints.pop_back();
+            }
+        }
+
+        self.checkpoints.push_back((self.step, jameboy.save_state()));
+        if self.checkpoints.len() > self.capacity {
+            self.checkpoints.pop_front();
+        }
+    }
+}
+
+fn load(jameboy: &mut Jameboy, state: &[u8]) {
+    jameboy
+        .load_state(state)
+        .expect("Checkpoints are written by this machine");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boot::Model;
+    use crate::cpu::Register;
+
+    fn init_jameboy() -> Jameboy {
+        let mut jameboy = Jameboy::init();
+        let mut rom = vec![0; 0x8000];
+        // loop: INC A; LD [HL], A; INC L; JR loop
+        rom[0x100..0x105].copy_from_slice(&[0x3C, 0x77, 0x2C, 0x18, 0xFB]);
+        jameboy.load_cartridge(&rom);
+        jameboy.skip_bootstrap_rom(Model::Dmg);
+
+        jameboy
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut jameboy = init_jameboy();
+        let mut timeline = Timeline::init(16, 10);
+
+        let mut states = Vec::new();
+        for step in 0..35 {
+            if step == 12 {
+                timeline.set_input(&mut jameboy, Buttons { a: true, ..Default::default() });
+            }
+            states.push(jameboy.save_state());
+            timeline.step(&mut jameboy);
+        }
+
+        for expected in states.iter().rev() {
+            assert!(timeline.step_back(&mut jameboy));
+            assert_eq!(*expected, jameboy.save_state());
+        }
+        assert_eq!(0, timeline.position());
+        assert!(!timeline.step_back(&mut jameboy));
+    }
+
+    #[test]
+    fn test_reverse_continue() {
+        let mut jameboy = init_jameboy();
+        let mut timeline = Timeline::init(16, 10);
+
+        for _ in 0..50 {
+            timeline.step(&mut jameboy);
+        }
+        let a = jameboy.cpu.read_register(Register::A);
+
+        // The store at 0x0101 last ran in this iteration of the loop, so A is unchanged
+        assert!(timeline.reverse_continue(&mut jameboy, |pc| pc == 0x0101));
+        assert_eq!(0x0101, jameboy.cpu.pc);
+        assert_eq!(a, jameboy.cpu.read_register(Register::A));
+
+        // Continuing again finds the previous iteration, 4 instructions earlier
+        let position = timeline.position();
+        assert!(timeline.reverse_continue(&mut jameboy, |pc| pc == 0x0101));
+        assert_eq!(position - 4, timeline.position());
+
+        // An address never reached leaves the machine where it was
+        let state = jameboy.save_state();
+        assert!(!timeline.reverse_continue(&mut jameboy, |pc| pc == 0x4000));
+        assert_eq!(state, jameboy.save_state());
+    }
+}
@@ -8,7 +8,7 @@
 /// # Examples
 ///
 /// ```
-/// use util::u8_to_u16;
+/// use jameboy::util::u8_to_u16;
 /// let result = u8_to_u16(0xAB, 0xCD);
 ///
 /// assert_eq!(0xABCD, result);
@@ -26,7 +26,7 @@ pub fn u8_to_u16(msb: u8, lsb: u8) -> u16 {
 /// # Examples
 ///
 /// ```
-/// use util::u16_to_u8;
+/// use jameboy::util::u16_to_u8;
 /// let (msb, lsb) = u16_to_u8(0xABCD);
 ///
 /// assert_eq!(0xAB, msb);
@@ -0,0 +1,46 @@
+//! Helpers shared by the test ROM harnesses.
+//!
+//! Test ROMs aren't checked in. They're looked up under `tests/roms`, or the directory in
+//! `JAMEBOY_TEST_ROMS`, and tests whose ROMs are missing are skipped rather than failed.
+
+use jameboy::boot::Model;
+use jameboy::Jameboy;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn rom_dir() -> PathBuf {
+    match env::var_os("JAMEBOY_TEST_ROMS") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/roms"),
+    }
+}
+
+/// Reads a file from the test ROM directory, or returns None after noting that it was skipped.
+pub fn read_test_file(path: &str) -> Option<Vec<u8>> {
+    let path = rom_dir().join(path);
+    match fs::read(&path) {
+        Ok(data) => Some(data),
+        Err(_) => {
+            eprintln!("skipping {}: not found", path.display());
+            None
+        }
+    }
+}
+
+/// Starts a cartridge from the DMG post-boot state.
+#[allow(dead_code)] // Not every harness boots a cartridge
+pub fn boot(rom: &[u8]) -> Jameboy {
+    let mut jameboy = Jameboy::init();
+    jameboy.load_cartridge(rom);
+    jameboy.skip_bootstrap_rom(Model::Dmg);
+    jameboy
+}
+
+/// Where harnesses write files that help diagnose a failure.
+#[allow(dead_code)] // Not every harness writes diagnostics
+pub fn output_dir() -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test-roms");
+    fs::create_dir_all(&dir).expect("Could not create test output directory");
+    dir
+}
@@ -0,0 +1,189 @@
+//! Runs PPU test ROMs for a fixed number of frames and compares the last frame against a reference
+//! screenshot, pixel by pixel. Each case is `<suite>/<name>.gb` with its reference at
+//! `<suite>/<name>.png` under the test ROM directory, see `common`.
+//!
+//! On a mismatch the frame that was drawn and a diff, with differing pixels in red, are written to
+//! the test output directory.
+//!
+//! Cases in `KNOWN_FAILURES` are expected to fail. Their mismatches are noted but don't fail the
+//! harness, while one that starts passing does, so it gets taken off the list.
+
+mod common;
+
+use image::{GrayImage, Luma, Rgb, RgbImage};
+use jameboy::ppu::PixelColour;
+
+// Every mealybug-tearoom test ROM, which also run long enough within this many frames
+const MEALYBUG_FRAMES: usize = 60;
+const MEALYBUG_TESTS: [&str; 24] = [
+    "m2_win_en_toggle",
+    "m3_bgp_change",
+    "m3_bgp_change_sprites",
+    "m3_lcdc_bg_en_change",
+    "m3_lcdc_bg_map_change",
+    "m3_lcdc_obj_en_change",
+    "m3_lcdc_obj_en_change_variant",
+    "m3_lcdc_obj_size_change",
+    "m3_lcdc_obj_size_change_scx",
+    "m3_lcdc_tile_sel_change",
+    "m3_lcdc_tile_sel_win_change",
+    "m3_lcdc_win_en_change_multiple",
+    "m3_lcdc_win_en_change_multiple_wx",
+    "m3_lcdc_win_map_change",
+    "m3_obp0_change",
+    "m3_scx_high_5_bits",
+    "m3_scx_low_3_bits",
+    "m3_scy_change",
+    "m3_window_timing",
+    "m3_window_timing_wx_0",
+    "m3_wx_4_change",
+    "m3_wx_4_change_sprites",
+    "m3_wx_5_change",
+    "m3_wx_6_change",
+];
+
+const PALETTES_IGNORED: &str = "BGP, OBP0 and OBP1 are ignored";
+const NO_WINDOW: &str = "BGP, OBP0 and OBP1 are ignored and the window is never drawn";
+
+/// Cases the PPU is known to draw wrong, as `<suite>/<name>`, and why.
+const KNOWN_FAILURES: [(&str, &str); 25] = [
+    ("dmg-acid2/dmg-acid2", NO_WINDOW),
+    ("mealybug/m2_win_en_toggle", NO_WINDOW),
+    ("mealybug/m3_bgp_change", PALETTES_IGNORED),
+    ("mealybug/m3_bgp_change_sprites", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_bg_en_change", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_bg_map_change", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_obj_en_change", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_obj_en_change_variant", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_obj_size_change", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_obj_size_change_scx", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_tile_sel_change", PALETTES_IGNORED),
+    ("mealybug/m3_lcdc_tile_sel_win_change", NO_WINDOW),
+    ("mealybug/m3_lcdc_win_en_change_multiple", NO_WINDOW),
+    ("mealybug/m3_lcdc_win_en_change_multiple_wx", NO_WINDOW),
+    ("mealybug/m3_lcdc_win_map_change", NO_WINDOW),
+    ("mealybug/m3_obp0_change", PALETTES_IGNORED),
+    ("mealybug/m3_scx_high_5_bits", PALETTES_IGNORED),
+    ("mealybug/m3_scx_low_3_bits", PALETTES_IGNORED),
+    ("mealybug/m3_scy_change", PALETTES_IGNORED),
+    ("mealybug/m3_window_timing", NO_WINDOW),
+    ("mealybug/m3_window_timing_wx_0", NO_WINDOW),
+    ("mealybug/m3_wx_4_change", NO_WINDOW),
+    ("mealybug/m3_wx_4_change_sprites", NO_WINDOW),
+    ("mealybug/m3_wx_5_change", NO_WINDOW),
+    ("mealybug/m3_wx_6_change", NO_WINDOW),
+];
+
+#[test]
+fn test_dmg_acid2() {
+    if let Some(failure) = check_golden("dmg-acid2", "dmg-acid2", 60) {
+        panic!("{}", failure);
+    }
+}
+
+#[test]
+fn test_mealybug_tearoom() {
+    let failures: Vec<String> = MEALYBUG_TESTS
+        .iter()
+        .filter_map(|name| check_golden("mealybug", name, MEALYBUG_FRAMES))
+        .collect();
+
+    assert!(failures.is_empty(), "{} failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+/// Runs a case and returns why the harness should fail because of it, if it should: an unexpected
+/// mismatch, or a known failure that now passes.
+fn check_golden(suite: &str, name: &str, frames: usize) -> Option<String> {
+    let case = format!("{}/{}", suite, name);
+    let known_failure = KNOWN_FAILURES
+        .iter()
+        .find(|(known, _)| *known == case)
+        .map(|(_, reason)| reason);
+
+    match (run_golden(suite, name, frames)?, known_failure) {
+        (Ok(()), None) => None,
+        (Err(failure), None) => Some(failure),
+        (Err(failure), Some(reason)) => {
+            eprintln!("known failure, {}: {}", reason, failure);
+            None
+        }
+        (Ok(()), Some(_)) => Some(format!("{}: passes now, take it off KNOWN_FAILURES", case)),
+    }
+}
+
+/// Returns a description of the mismatch if the frame doesn't match the reference, or None if the
+/// ROM or reference is missing and the case was skipped.
+fn run_golden(suite: &str, name: &str, frames: usize) -> Option<Result<(), String>> {
+    let rom = common::read_test_file(&format!("{}/{}.gb", suite, name))?;
+    let reference = common::read_test_file(&format!("{}/{}.png", suite, name))?;
+
+    Some(compare_golden(name, &rom, &reference, frames))
+}
+
+fn compare_golden(name: &str, rom: &[u8], reference: &[u8], frames: usize) -> Result<(), String> {
+    let reference = image::load_from_memory(reference)
+        .map_err(|e| format!("{}: could not decode reference: {}", name, e))?
+        .into_luma8();
+
+    let mut jameboy = common::boot(rom);
+    for _ in 0..frames {
+        jameboy.run_frame();
+    }
+    let actual = jameboy.framebuffer();
+
+    if actual.dimensions() != reference.dimensions() {
+        return Err(format!(
+            "{}: reference is {:?}, frame is {:?}",
+            name,
+            reference.dimensions(),
+            actual.dimensions()
+        ));
+    }
+
+    let (diff, mismatches) = diff(actual, &reference);
+    if mismatches == 0 {
+        return Ok(());
+    }
+
+    let output_dir = common::output_dir();
+    let actual_path = output_dir.join(format!("{}.actual.png", name));
+    let diff_path = output_dir.join(format!("{}.diff.png", name));
+    actual.save(&actual_path).expect("Could not save frame");
+    diff.save(&diff_path).expect("Could not save diff");
+
+    Err(format!(
+        "{}: {} pixels differ, see {} and {}",
+        name,
+        mismatches,
+        actual_path.display(),
+        diff_path.display()
+    ))
+}
+
+/// Reference screenshots use whatever four grays their authors picked, so compare shades rather
+/// than exact values.
+fn reference_shade(luma: Luma<u8>) -> PixelColour {
+    match luma.0[0] {
+        0xC0..=0xFF => PixelColour::White,
+        0x80..=0xBF => PixelColour::LightGray,
+        0x40..=0x7F => PixelColour::DarkGray,
+        _ => PixelColour::Black,
+    }
+}
+
+/// Shades matching pixels dimly and differing ones red, and counts the differences.
+fn diff(actual: &GrayImage, reference: &GrayImage) -> (RgbImage, usize) {
+    let mut mismatches = 0;
+    let diff = RgbImage::from_fn(actual.width(), actual.height(), |x, y| {
+        let actual = *actual.get_pixel(x, y);
+        if PixelColour::from_grayscale(actual) == reference_shade(*reference.get_pixel(x, y)) {
+            let dimmed = 128 + actual.0[0] / 2;
+            Rgb([dimmed, dimmed, dimmed])
+        } else {
+            mismatches += 1;
+            Rgb([255, 0, 0])
+        }
+    });
+
+    (diff, mismatches)
+}
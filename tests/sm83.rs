@@ -0,0 +1,198 @@
+//! Runs every opcode against the SM83 single-step test vectors. Each `<opcode>.json` or
+//! `cb <opcode>.json` file under `sm83/` in the test ROM directory, see `common`, holds cases with
+//! an initial and final CPU and RAM state, and the bus activity of every M-cycle in between.
+//!
+//! Every M-cycle's read or write is checked against the bus activity, along with the final state.
+//! As nothing is reported when a test passes, the number of cases run, or that the vectors are
+//! missing, is written straight to stderr where captured test output doesn't hide it.
+
+mod common;
+
+use jameboy::bus::{Access, TestBus};
+use jameboy::cpu::Cpu;
+use jameboy::memory::{Address, Memory};
+use jameboy::opcode::{self, Opcode};
+use serde::Deserialize;
+use std::io::{self, Write};
+
+const ADDRESS_BOOTSTRAP_ROM_DISABLE: u16 = 0xFF50;
+const ADDRESS_INTERRUPT_ENABLE_REGISTER: u16 = 0xFFFF;
+const OPCODE_PREFIX: u8 = 0xCB;
+
+#[derive(Deserialize)]
+struct Case {
+    name: String,
+    initial: State,
+    #[serde(rename = "final")]
+    expected: State,
+    cycles: Vec<Option<Cycle>>,
+}
+
+/// The address and data pins during an M-cycle, and the pins, like `r-m` for a memory read or `-wm`
+/// for a write.
+#[derive(Deserialize)]
+struct Cycle(u16, Option<u8>, String);
+
+impl Cycle {
+    /// What the bus should have seen. Cycles with neither a memory read nor a write are internal,
+    /// whatever happens to be on the address and data pins.
+    fn access(cycle: &Option<Cycle>) -> Access {
+        match cycle {
+            Some(Cycle(address, Some(value), pins)) if pins.contains('m') && pins.contains('r') => {
+                Access::Read { address: *address, value: *value }
+            }
+            Some(Cycle(address, Some(value), pins)) if pins.contains('m') && pins.contains('w') => {
+                Access::Write { address: *address, value: *value }
+            }
+            _ => Access::Internal,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+struct State {
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    h: u8,
+    l: u8,
+    pc: u16,
+    sp: u16,
+    ime: u8,
+    ie: Option<u8>,
+    ram: Vec<(u16, u8)>,
+}
+
+#[test]
+fn test_sm83() {
+    let mut files = Vec::new();
+    for byte in 0..=u8::MAX {
+        if byte != OPCODE_PREFIX {
+            files.push((format!("{:02x}.json", byte), opcode::decode(byte), 1));
+        }
+        files.push((format!("cb {:02x}.json", byte), opcode::decode_prefixed(byte), 2));
+    }
+
+    let mut files_run = 0;
+    let mut cases_run = 0;
+    let mut failures = Vec::new();
+    for (name, opcode, prefix_bytes) in files {
+        match run_file(&name, opcode, prefix_bytes) {
+            Some(Ok(cases)) => {
+                files_run += 1;
+                cases_run += cases;
+            }
+            Some(Err(failure)) => {
+                files_run += 1;
+                failures.push(failure);
+            }
+            None => {}
+        }
+    }
+
+    let summary = if files_run == 0 {
+        format!("sm83: skipped, no test vectors in {}", common::rom_dir().join("sm83").display())
+    } else {
+        format!("sm83: {} cases passed from {} files", cases_run, files_run)
+    };
+    let _ = writeln!(io::stderr(), "{}", summary);
+
+    assert!(failures.is_empty(), "{} opcodes failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+/// Returns how many cases of a file passed, or the first that failed, or None if the file is missing.
+/// `prefix_bytes` is how many bytes precede the operands, which `decode_prefixed` opcodes don't count
+/// in their size. Each takes an M-cycle to fetch, which happens before the handler runs.
+fn run_file(name: &str, opcode: Option<&Opcode>, prefix_bytes: u16) -> Option<Result<usize, String>> {
+    let data = common::read_test_file(&format!("sm83/{}", name))?;
+    let cases: Vec<Case> = match serde_json::from_slice(&data) {
+        Ok(cases) => cases,
+        Err(e) => return Some(Err(format!("{}: could not parse: {}", name, e))),
+    };
+    let Some(opcode) = opcode else {
+        return Some(Err(format!("{}: not decoded", name)));
+    };
+
+    let failure = cases.iter().find_map(|case| {
+        let (actual, accesses) = run_case(opcode, case, prefix_bytes);
+        let expected_accesses: Vec<Access> = case.cycles.iter().map(Cycle::access).collect();
+        if actual != case.expected {
+            Some(format!(
+                "{} ({}):\n  expected {:?}\n  actual   {:?}",
+                case.name,
+                opcode.mnemonic,
+                case.expected,
+                actual
+            ))
+        } else if accesses != expected_accesses {
+            Some(format!(
+                "{} ({}):\n  expected bus {:?}\n  actual bus   {:?}",
+                case.name,
+                opcode.mnemonic,
+                expected_accesses,
+                accesses
+            ))
+        } else {
+            None
+        }
+    });
+
+    Some(failure.map_or(Ok(cases.len()), Err))
+}
+
+/// Returns the final state and the bus activity of every M-cycle, including fetching the opcode.
+fn run_case(opcode: &Opcode, case: &Case, prefix_bytes: u16) -> (State, Vec<Access>) {
+    let initial = &case.initial;
+    let mut cpu = Cpu::init();
+    cpu.a = initial.a;
+    cpu.b = initial.b;
+    cpu.c = initial.c;
+    cpu.d = initial.d;
+    cpu.e = initial.e;
+    cpu.f = initial.f;
+    cpu.h = initial.h;
+    cpu.l = initial.l;
+    cpu.pc = initial.pc;
+    cpu.sp = initial.sp;
+    cpu.ime = initial.ime != 0;
+
+    let mut memory = Memory::init();
+    memory.write(Address(ADDRESS_BOOTSTRAP_ROM_DISABLE), 1);
+    if let Some(ie) = initial.ie {
+        memory.write(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER), ie);
+    }
+    for &(address, value) in &initial.ram {
+        memory.write(Address(address), value);
+    }
+
+    cpu.pc = cpu.pc.wrapping_add(prefix_bytes + opcode.size_bytes as u16 - 1);
+    let mut bus = TestBus::init(memory);
+    for offset in 0..prefix_bytes {
+        let address = initial.pc.wrapping_add(offset);
+        bus.accesses.push(Access::Read { address, value: bus.memory.read(Address(address)) });
+    }
+    opcode.execute(&mut cpu, &mut bus);
+
+    let memory = bus.memory;
+    let expected = &case.expected;
+    let state = State {
+        a: cpu.a,
+        b: cpu.b,
+        c: cpu.c,
+        d: cpu.d,
+        e: cpu.e,
+        f: cpu.f,
+        h: cpu.h,
+        l: cpu.l,
+        pc: cpu.pc,
+        sp: cpu.sp,
+        ime: cpu.ime as u8,
+        ie: expected.ie.map(|_| memory.read(Address(ADDRESS_INTERRUPT_ENABLE_REGISTER))),
+        ram: expected.ram.iter().map(|&(address, _)| (address, memory.read(Address(address)))).collect(),
+    };
+
+    (state, bus.accesses)
+}